@@ -0,0 +1,187 @@
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+
+/// A single post-processing step applied to generated text
+#[derive(Clone, Debug)]
+pub enum OutputFilter {
+    /// Remove chat-template tokens such as `<|im_start|>` or `<|endoftext|>`
+    StripChatMarkers,
+    /// Curly quotes become straight ones and quotes wrapping the whole text are dropped
+    NormalizeQuotes,
+    /// Collapse runs of whitespace into single spaces and trim the ends
+    NormalizeWhitespace,
+    /// Cut the text at the last sentence boundary that fits into `max_chars`
+    MaxLength { max_chars: usize },
+    /// Reject the text if any of these words or phrases appear (case-insensitive)
+    BanWords(Vec<String>),
+    /// Reject the text if it repeats `window` consecutive words of `reference`
+    RejectPromptLeak { reference: String, window: usize },
+}
+
+/// Reason a generated text was refused by an [`OutputFilterPipeline`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterRejection {
+    BannedWord(String),
+    PromptLeak(String),
+    Empty,
+}
+
+impl fmt::Display for FilterRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterRejection::BannedWord(word) => write!(f, "banned word: {word}"),
+            FilterRejection::PromptLeak(fragment) => write!(f, "system prompt leak: {fragment}"),
+            FilterRejection::Empty => write!(f, "empty output"),
+        }
+    }
+}
+
+impl std::error::Error for FilterRejection {}
+
+/// Ordered list of filters applied to every generated text before it is delivered
+#[derive(Clone, Debug, Default)]
+pub struct OutputFilterPipeline {
+    pub filters: Vec<OutputFilter>,
+}
+
+impl OutputFilterPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, filter: OutputFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Run all filters in order, returning the cleaned text or the first rejection
+    pub fn apply(&self, text: &str) -> Result<String, FilterRejection> {
+        let mut output = text.to_string();
+
+        for filter in &self.filters {
+            output = filter.apply(&output)?;
+        }
+
+        if output.trim().is_empty() {
+            return Err(FilterRejection::Empty);
+        }
+
+        Ok(output)
+    }
+}
+
+impl OutputFilter {
+    pub fn apply(&self, text: &str) -> Result<String, FilterRejection> {
+        match self {
+            OutputFilter::StripChatMarkers => Ok(strip_chat_markers(text)),
+            OutputFilter::NormalizeQuotes => Ok(normalize_quotes(text)),
+            OutputFilter::NormalizeWhitespace => Ok(normalize_whitespace(text)),
+            OutputFilter::MaxLength { max_chars } => Ok(truncate_at_sentence(text, *max_chars)),
+            OutputFilter::BanWords(words) => match find_banned_word(text, words) {
+                Some(word) => Err(FilterRejection::BannedWord(word)),
+                None => Ok(text.to_string()),
+            },
+            OutputFilter::RejectPromptLeak { reference, window } => {
+                match find_leaked_fragment(text, reference, *window) {
+                    Some(fragment) => Err(FilterRejection::PromptLeak(fragment)),
+                    None => Ok(text.to_string()),
+                }
+            }
+        }
+    }
+}
+
+fn strip_chat_markers(text: &str) -> String {
+    let re = Regex::new(r"<\|[a-zA-Z_]+\|>").unwrap();
+    let stripped = re.replace_all(text, "");
+
+    // A leftover role header from the template, e.g. "assistant\n"
+    let stripped = stripped.trim_start();
+    stripped
+        .strip_prefix("assistant")
+        .map(|rest| rest.trim_start())
+        .unwrap_or(stripped)
+        .to_string()
+}
+
+fn normalize_quotes(text: &str) -> String {
+    let normalized: String = text
+        .chars()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{2032}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{2033}' => '"',
+            _ => c,
+        })
+        .collect();
+
+    let trimmed = normalized.trim();
+    if trimmed.len() >= 2
+        && trimmed.starts_with('"')
+        && trimmed.ends_with('"')
+        && trimmed.matches('"').count() == 2
+    {
+        trimmed[1..trimmed.len() - 1].to_string()
+    } else {
+        normalized
+    }
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate_at_sentence(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let head: String = text.chars().take(max_chars).collect();
+
+    // Prefer the last complete sentence that fits
+    if let Some(end) = head.rfind(['.', '!', '?', '…']) {
+        let end = end + head[end..].chars().next().map_or(1, char::len_utf8);
+        return head[..end].trim_end().to_string();
+    }
+
+    // Otherwise cut at the last word and mark the truncation
+    let ellipsis_room: String = head.chars().take(max_chars.saturating_sub(1)).collect();
+    match ellipsis_room.rfind(char::is_whitespace) {
+        Some(space) => format!("{}…", ellipsis_room[..space].trim_end()),
+        None => format!("{ellipsis_room}…"),
+    }
+}
+
+fn normalize_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn find_banned_word(text: &str, banned: &[String]) -> Option<String> {
+    let haystack = format!(" {} ", normalize_words(text).join(" "));
+
+    banned
+        .iter()
+        .find(|word| {
+            let needle = normalize_words(word).join(" ");
+            !needle.is_empty() && haystack.contains(&format!(" {needle} "))
+        })
+        .cloned()
+}
+
+fn find_leaked_fragment(text: &str, reference: &str, window: usize) -> Option<String> {
+    if window == 0 {
+        return None;
+    }
+
+    let reference_words = normalize_words(reference);
+    let reference_windows: HashSet<&[String]> = reference_words.windows(window).collect();
+
+    normalize_words(text)
+        .windows(window)
+        .find(|fragment| reference_windows.contains(fragment))
+        .map(|fragment| fragment.join(" "))
+}
@@ -0,0 +1,270 @@
+use regex::Regex;
+use std::fmt;
+
+/// Kind of harmful content detected in a generated text
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SafetyCategory {
+    /// Methods, means or step-by-step instructions for self-harm
+    SelfHarmInstructions,
+    /// Expressed intent or plans to end one's life
+    SuicidalIntent,
+    /// Descriptions of actively injuring oneself
+    SelfInjury,
+    /// Flagged by the model-based check rather than a local rule
+    ModelFlagged,
+}
+
+impl fmt::Display for SafetyCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafetyCategory::SelfHarmInstructions => write!(f, "self-harm instructions"),
+            SafetyCategory::SuicidalIntent => write!(f, "suicidal intent"),
+            SafetyCategory::SelfInjury => write!(f, "self-injury"),
+            SafetyCategory::ModelFlagged => write!(f, "flagged by model check"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SafetyVerdict {
+    Safe,
+    Flagged {
+        category: SafetyCategory,
+        matched: String,
+    },
+}
+
+impl SafetyVerdict {
+    pub fn is_flagged(&self) -> bool {
+        matches!(self, SafetyVerdict::Flagged { .. })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SafetyRule {
+    pub category: SafetyCategory,
+    pub pattern: Regex,
+}
+
+/// Local rule-based classifier, runs on every output without touching the model
+#[derive(Clone, Debug)]
+pub struct SafetyClassifier {
+    pub rules: Vec<SafetyRule>,
+}
+
+impl Default for SafetyClassifier {
+    fn default() -> Self {
+        let rules = [
+            (
+                SafetyCategory::SelfHarmInstructions,
+                r"(?i)\b(how (to|do i|can i)|ways? to|best way to|steps? to)\b.{0,40}\b(kill (my|your)self|end (my|your) life|hurt (my|your)self|cut (my|your)self|overdose|hang (my|your)self|suicide)",
+            ),
+            (
+                SafetyCategory::SelfHarmInstructions,
+                r"(?i)\b(lethal|deadly|fatal) (dose|amount|quantity)\b",
+            ),
+            (
+                SafetyCategory::SelfHarmInstructions,
+                r"(?i)\b(how many|enough) (pills|tablets|sleeping pills)\b",
+            ),
+            (
+                SafetyCategory::SuicidalIntent,
+                r"(?i)\b(kill(ing)? myself|end(ing)? (my|it) (life|all)|take my (own )?life|commit(ting)? suicide|want(ed)? to die|better off dead|suicide note)\b",
+            ),
+            (
+                SafetyCategory::SuicidalIntent,
+                r"(?i)\b(no reason to (live|go on)|won'?t be here tomorrow|say(ing)? goodbye forever)\b",
+            ),
+            (
+                SafetyCategory::SelfInjury,
+                r"(?i)\b(cut(ting)?|burn(ing)?|hurt(ing)?|harm(ing)?) myself\b",
+            ),
+            (
+                SafetyCategory::SelfInjury,
+                r"(?i)\b(self[- ]harm|self[- ]injur(y|e))\b",
+            ),
+        ];
+
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|(category, pattern)| SafetyRule {
+                    category,
+                    pattern: Regex::new(pattern).unwrap(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SafetyClassifier {
+    pub fn classify(&self, text: &str) -> SafetyVerdict {
+        self.rules
+            .iter()
+            .find_map(|rule| {
+                rule.pattern.find(text).map(|m| SafetyVerdict::Flagged {
+                    category: rule.category,
+                    matched: m.as_str().to_string(),
+                })
+            })
+            .unwrap_or(SafetyVerdict::Safe)
+    }
+}
+
+/// What happens to flagged outputs of a request
+#[derive(Clone, Debug)]
+pub struct SafetyPolicy {
+    pub classifier: SafetyClassifier,
+    /// Also ask the model itself to judge outputs that passed the local rules
+    pub model_check: bool,
+    /// Replacement text delivered instead of a flagged output
+    pub safe_text: String,
+    /// Optional pointer to help, delivered alongside the replacement text
+    pub support_resource: Option<String>,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self {
+            classifier: SafetyClassifier::default(),
+            model_check: false,
+            safe_text: "I need to slow down and breathe. This feeling will pass.".to_string(),
+            support_resource: None,
+        }
+    }
+}
+
+/// Attached to a response whose original text was replaced by the safety policy
+#[derive(Clone, Debug)]
+pub struct SafetyIntervention {
+    pub category: SafetyCategory,
+    pub support_resource: Option<String>,
+}
+
+pub(crate) const MODEL_CHECK_SYSTEM_PROMPT: &str = "You are a content safety classifier for a video game. \
+Decide whether the text describes methods of self-harm or suicide, expresses intent to self-harm or \
+die by suicide, or encourages it. Answer with exactly one word: SAFE or UNSAFE.";
+
+/// Interpret the reply of the model-based check, anything but a clear SAFE counts as flagged
+pub(crate) fn parse_model_verdict(reply: &str) -> SafetyVerdict {
+    let reply = reply.trim().to_uppercase();
+    if reply.starts_with("SAFE") {
+        SafetyVerdict::Safe
+    } else {
+        SafetyVerdict::Flagged {
+            category: SafetyCategory::ModelFlagged,
+            matched: reply,
+        }
+    }
+}
+
+/// Verdict of the model-based check, a check that didn't answer can't vouch for the text
+pub(crate) fn model_check_verdict(reply: Option<&str>) -> SafetyVerdict {
+    match reply {
+        Some(reply) => parse_model_verdict(reply),
+        None => SafetyVerdict::Flagged {
+            category: SafetyCategory::ModelFlagged,
+            matched: "model check failed".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(text: &str) -> Option<SafetyCategory> {
+        match SafetyClassifier::default().classify(text) {
+            SafetyVerdict::Safe => None,
+            SafetyVerdict::Flagged { category, .. } => Some(category),
+        }
+    }
+
+    #[test]
+    fn self_harm_instructions_are_flagged() {
+        for text in [
+            "What is the best way to end my life quietly",
+            "Steps to hurt myself without anyone noticing",
+            "I looked up the lethal dose of that medicine",
+            "How many sleeping pills would it take",
+        ] {
+            assert_eq!(
+                category(text),
+                Some(SafetyCategory::SelfHarmInstructions),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn suicidal_intent_is_flagged() {
+        for text in [
+            "I just want to die",
+            "Everyone would be better off dead without me",
+            "There is no reason to go on",
+            "Maybe I won't be here tomorrow",
+            "Thinking about killing myself again",
+        ] {
+            assert_eq!(
+                category(text),
+                Some(SafetyCategory::SuicidalIntent),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn self_injury_is_flagged() {
+        for text in [
+            "I keep cutting myself when it gets loud",
+            "The urge to self-harm is back",
+            "Burning myself made the voices stop",
+        ] {
+            assert_eq!(category(text), Some(SafetyCategory::SelfInjury), "{text}");
+        }
+    }
+
+    #[test]
+    fn everyday_thoughts_pass() {
+        for text in [
+            "I should cut the vegetables before the water boils",
+            "Dying to know how the show ends",
+            "The deadline is killing me",
+            "How to fall asleep faster",
+            "I burned the toast again",
+            "Two tablets of vitamin C with breakfast",
+        ] {
+            assert_eq!(category(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn flagged_verdict_carries_the_matched_text() {
+        let verdict = SafetyClassifier::default().classify("Honestly I want to die today");
+        assert_eq!(
+            verdict,
+            SafetyVerdict::Flagged {
+                category: SafetyCategory::SuicidalIntent,
+                matched: "want to die".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn model_check_only_passes_a_clear_safe() {
+        assert_eq!(model_check_verdict(Some(" safe\n")), SafetyVerdict::Safe);
+        assert!(model_check_verdict(Some("UNSAFE")).is_flagged());
+        assert!(model_check_verdict(Some("I am not sure")).is_flagged());
+    }
+
+    #[test]
+    fn failed_model_check_flags_the_text() {
+        assert_eq!(
+            model_check_verdict(None),
+            SafetyVerdict::Flagged {
+                category: SafetyCategory::ModelFlagged,
+                matched: "model check failed".to_string(),
+            }
+        );
+    }
+}
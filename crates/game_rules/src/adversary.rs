@@ -0,0 +1,410 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::*;
+use crate::engine::Game;
+use crate::state::*;
+
+/// Turns of adversary history the director looks back on
+const DIRECTOR_MEMORY: u32 = 3;
+
+/// Decides which card the adversary plays from its hand
+pub trait AdversaryStrategy: Send + Sync {
+    fn name(&self) -> String;
+
+    /// Index into `hand` of the card to play, `hand` is never empty
+    fn choose_card(&mut self, game: &Game, hand: &[SchizophrenicCard], rng: &mut StdRng) -> usize;
+}
+
+/// The available strategies, picked by the difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AdversaryStrategyKind {
+    Random,
+    WeakestResource,
+    Escalating,
+    #[default]
+    Director,
+}
+
+impl AdversaryStrategyKind {
+    pub fn build(self) -> Box<dyn AdversaryStrategy> {
+        match self {
+            AdversaryStrategyKind::Random => Box::new(RandomStrategy),
+            AdversaryStrategyKind::WeakestResource => Box::new(WeakestResourceStrategy),
+            AdversaryStrategyKind::Escalating => Box::new(EscalatingStrategy),
+            AdversaryStrategyKind::Director => Box::new(DirectorStrategy),
+        }
+    }
+}
+
+/// How hard a card hits the player, resources lost plus a flat amount per lasting effect
+pub fn card_harm(card: &SchizophrenicCard) -> f32 {
+    let effects = &card.effects;
+    let resources_lost = -(effects.player_sleep_change
+        + effects.player_health_change
+        + effects.player_mental_change
+        + effects.player_food_change);
+
+    resources_lost
+        + 10.0 * effects.status_effects.len() as f32
+        + 10.0 * effects.environmental_changes.len() as f32
+        + 5.0 * card.conditions.trigger_symptoms.len() as f32
+}
+
+/// Index of the card with the highest value, the first one wins ties
+fn best_card(hand: &[SchizophrenicCard], value: impl Fn(&SchizophrenicCard) -> f32) -> usize {
+    hand.iter()
+        .enumerate()
+        .map(|(index, card)| (index, value(card)))
+        .fold((0, f32::NEG_INFINITY), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// Plays any card from the hand
+pub struct RandomStrategy;
+
+impl AdversaryStrategy for RandomStrategy {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn choose_card(&mut self, _game: &Game, hand: &[SchizophrenicCard], rng: &mut StdRng) -> usize {
+        rng.random_range(0..hand.len())
+    }
+}
+
+/// Goes after whichever resource the player is shortest on
+pub struct WeakestResourceStrategy;
+
+impl WeakestResourceStrategy {
+    fn damage_to(card: &SchizophrenicCard, resource_type: ResourceType) -> f32 {
+        let effects = &card.effects;
+        let change = match resource_type {
+            ResourceType::Sleep => effects.player_sleep_change,
+            ResourceType::Health => effects.player_health_change,
+            ResourceType::Mental => effects.player_mental_change,
+            ResourceType::Food => effects.player_food_change,
+        };
+        let locks = effects
+            .environmental_changes
+            .iter()
+            .filter(|change| {
+                matches!(change, EnvironmentalChange::LockResource { resource, .. } if *resource == resource_type)
+            })
+            .count();
+
+        -change + 15.0 * locks as f32
+    }
+}
+
+impl AdversaryStrategy for WeakestResourceStrategy {
+    fn name(&self) -> String {
+        "weakest-resource".to_string()
+    }
+
+    fn choose_card(&mut self, game: &Game, hand: &[SchizophrenicCard], _rng: &mut StdRng) -> usize {
+        let weakest = ResourceType::ALL
+            .into_iter()
+            .min_by(|a, b| {
+                game.state
+                    .get_resource_value(*a)
+                    .total_cmp(&game.state.get_resource_value(*b))
+            })
+            .unwrap_or(ResourceType::Mental);
+
+        // Cards that don't touch the weakest resource still compete on overall harm
+        best_card(hand, |card| {
+            3.0 * Self::damage_to(card, weakest) + card_harm(card)
+        })
+    }
+}
+
+/// Mild cards while the player is stable, the harshest ones once a crisis sets in
+pub struct EscalatingStrategy;
+
+impl AdversaryStrategy for EscalatingStrategy {
+    fn name(&self) -> String {
+        "escalating".to_string()
+    }
+
+    fn choose_card(&mut self, game: &Game, hand: &[SchizophrenicCard], _rng: &mut StdRng) -> usize {
+        let mut by_harm: Vec<usize> = (0..hand.len()).collect();
+        by_harm.sort_by(|a, b| card_harm(&hand[*a]).total_cmp(&card_harm(&hand[*b])));
+
+        let escalation =
+            game.state.crisis_level as usize as f32 / CrisisLevel::Critical as usize as f32;
+        let rank = (escalation * (hand.len() - 1) as f32).round() as usize;
+        by_harm[rank]
+    }
+}
+
+/// Paces tension over the days: pressure builds towards the night and from day to day,
+/// and eases off after a run of harsh cards
+pub struct DirectorStrategy;
+
+impl DirectorStrategy {
+    fn tension_target(game: &Game) -> f32 {
+        let state = &game.state;
+        let day_ramp = (state.current_day as f32 * 4.0).min(30.0);
+        let time_of_day = match state.time_of_day {
+            TimeOfDay::EarlyMorning | TimeOfDay::Morning => 0.0,
+            TimeOfDay::Afternoon => 5.0,
+            TimeOfDay::Evening => 10.0,
+            TimeOfDay::Night => 20.0,
+            TimeOfDay::LateNight => 25.0,
+        };
+
+        day_ramp + time_of_day
+    }
+
+    fn recent_pressure(game: &Game) -> f32 {
+        let turn = game.phase.turn_number;
+        let recent_harm: f32 = game
+            .state
+            .adversary_actions
+            .iter()
+            .rev()
+            .take_while(|action| action.turn + DIRECTOR_MEMORY > turn)
+            .map(|action| card_harm(&action.card_played))
+            .sum();
+
+        recent_harm / DIRECTOR_MEMORY as f32
+    }
+}
+
+impl AdversaryStrategy for DirectorStrategy {
+    fn name(&self) -> String {
+        "director".to_string()
+    }
+
+    fn choose_card(&mut self, game: &Game, hand: &[SchizophrenicCard], _rng: &mut StdRng) -> usize {
+        // Whatever the recent cards haven't used up of the target is left for this one
+        let budget = (Self::tension_target(game) - Self::recent_pressure(game)).max(0.0);
+        best_card(hand, |card| -(card_harm(card) - budget).abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::state::AdversaryConditions;
+
+    fn card(id: u32, effects: AdversaryEffects) -> SchizophrenicCard {
+        SchizophrenicCard {
+            id,
+            card_name: format!("card_{id}"),
+            scenario_id: String::new(),
+            title: format!("Card {id}"),
+            card_type: "spectrum".to_string(),
+            conditions: SpectrumConditions::default(),
+            setup: String::new(),
+            image_prompt: String::new(),
+            adversary_type: AdversaryCardType::default(),
+            adversary_conditions: AdversaryConditions::default(),
+            effects,
+        }
+    }
+
+    /// A card whose only effect is losing `harm` mental health
+    fn card_with_harm(id: u32, harm: f32) -> SchizophrenicCard {
+        card(
+            id,
+            AdversaryEffects {
+                player_mental_change: -harm,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn choose(
+        strategy: &mut dyn AdversaryStrategy,
+        game: &Game,
+        hand: &[SchizophrenicCard],
+    ) -> u32 {
+        let index = strategy.choose_card(game, hand, &mut StdRng::seed_from_u64(0));
+        hand[index].id
+    }
+
+    #[test]
+    fn harm_counts_resources_lost_and_lasting_effects() {
+        let mut harsh = card(
+            1,
+            AdversaryEffects {
+                player_sleep_change: -10.0,
+                player_mental_change: -5.0,
+                status_effects: vec![StatusEffectApplication {
+                    effect: StatusEffect::Anxious,
+                    duration: 2,
+                    intensity: 1.0,
+                }],
+                environmental_changes: vec![EnvironmentalChange::RemoveObject {
+                    object: "phone".to_string(),
+                }],
+                ..Default::default()
+            },
+        );
+        harsh.conditions.trigger_symptoms = vec!["paranoia".to_string(), "anxiety".to_string()];
+        assert_eq!(card_harm(&harsh), 15.0 + 10.0 + 10.0 + 2.0 * 5.0);
+
+        // What the card gives back offsets what it takes
+        let mixed = card(
+            2,
+            AdversaryEffects {
+                player_food_change: -10.0,
+                player_health_change: 4.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(card_harm(&mixed), 6.0);
+    }
+
+    #[test]
+    fn weakest_resource_goes_after_the_lowest_resource() {
+        let mut game = Game::new();
+        game.state.food = 10.0;
+        let hand = [
+            card_with_harm(1, 20.0),
+            card(
+                2,
+                AdversaryEffects {
+                    player_food_change: -10.0,
+                    ..Default::default()
+                },
+            ),
+            card(
+                3,
+                AdversaryEffects {
+                    environmental_changes: vec![EnvironmentalChange::LockResource {
+                        resource: ResourceType::Food,
+                        turns: 2,
+                        kind: LockKind::NoGain,
+                    }],
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        // Locking the food away outweighs taking 10 of it
+        assert_eq!(choose(&mut WeakestResourceStrategy, &game, &hand), 3);
+        assert_eq!(choose(&mut WeakestResourceStrategy, &game, &hand[..2]), 2);
+    }
+
+    #[test]
+    fn weakest_resource_ties_go_to_the_first_resource_and_card() {
+        let mut game = Game::new();
+        game.state.sleep = 20.0;
+        game.state.food = 20.0;
+        let food = card(
+            1,
+            AdversaryEffects {
+                player_food_change: -10.0,
+                ..Default::default()
+            },
+        );
+        let sleep = card(
+            2,
+            AdversaryEffects {
+                player_sleep_change: -10.0,
+                ..Default::default()
+            },
+        );
+
+        // Sleep comes before food, so it is the one targeted
+        assert_eq!(
+            choose(&mut WeakestResourceStrategy, &game, &[food, sleep]),
+            2
+        );
+        assert_eq!(
+            choose(
+                &mut WeakestResourceStrategy,
+                &game,
+                &[card_with_harm(3, 10.0), card_with_harm(4, 10.0)]
+            ),
+            3
+        );
+    }
+
+    #[test]
+    fn escalating_picks_harsher_cards_as_the_crisis_deepens() {
+        let hand = [
+            card_with_harm(1, 30.0),
+            card_with_harm(2, 10.0),
+            card_with_harm(3, 20.0),
+            card_with_harm(4, 40.0),
+        ];
+        let mut game = Game::new();
+
+        for (crisis_level, expected) in [
+            (CrisisLevel::None, 2),
+            (CrisisLevel::Mild, 3),
+            (CrisisLevel::Moderate, 1),
+            (CrisisLevel::Severe, 1),
+            (CrisisLevel::Critical, 4),
+        ] {
+            game.state.crisis_level = crisis_level;
+            assert_eq!(
+                choose(&mut EscalatingStrategy, &game, &hand),
+                expected,
+                "{crisis_level:?}"
+            );
+        }
+
+        // A single card is played whatever the crisis
+        assert_eq!(choose(&mut EscalatingStrategy, &game, &hand[..1]), 1);
+    }
+
+    #[test]
+    fn director_plays_the_card_closest_to_its_budget() {
+        let hand = [
+            card_with_harm(1, 30.0),
+            card_with_harm(2, 5.0),
+            card_with_harm(3, 20.0),
+        ];
+
+        // Morning of the first day, the target is only the day ramp
+        let mut game = Game::new();
+        assert_eq!(DirectorStrategy::tension_target(&game), 4.0);
+        assert_eq!(choose(&mut DirectorStrategy, &game, &hand), 2);
+
+        game.state.current_day = 3;
+        game.state.time_of_day = TimeOfDay::Night;
+        assert_eq!(DirectorStrategy::tension_target(&game), 32.0);
+        assert_eq!(choose(&mut DirectorStrategy, &game, &hand), 1);
+    }
+
+    #[test]
+    fn director_eases_off_after_recent_harsh_cards() {
+        let hand = [
+            card_with_harm(1, 30.0),
+            card_with_harm(2, 5.0),
+            card_with_harm(3, 20.0),
+        ];
+        let mut game = Game::new();
+        game.state.current_day = 3;
+        game.state.time_of_day = TimeOfDay::Night;
+        game.phase.turn_number = 5;
+
+        let played = |turn| AdversaryAction {
+            turn,
+            card_played: card_with_harm(9, 30.0),
+            effects_applied: AdversaryEffects::default(),
+            timestamp: 0.0,
+        };
+        // Out of the director's memory, it doesn't count
+        game.state.adversary_actions.push(played(2));
+        assert_eq!(DirectorStrategy::recent_pressure(&game), 0.0);
+
+        game.state.adversary_actions.push(played(5));
+        assert_eq!(DirectorStrategy::recent_pressure(&game), 10.0);
+        assert_eq!(choose(&mut DirectorStrategy, &game, &hand), 3);
+    }
+}
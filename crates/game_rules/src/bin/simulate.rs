@@ -0,0 +1,105 @@
+//! Headless balance simulator, plays many games against the real content and
+//! reports outcome rates and per-card statistics.
+//!
+//! ```sh
+//! cargo run --release --manifest-path crates/game_rules/Cargo.toml --bin simulate -- --games 5000
+//! ```
+
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use game_rules::{
+    AdversaryStrategyKind, GameContent, GreedyPolicy, LookaheadPolicy, Policy, RandomPolicy,
+    Simulator,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum PolicyKind {
+    Random,
+    Greedy,
+    Lookahead,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum AdversaryKind {
+    Random,
+    WeakestResource,
+    Escalating,
+    Director,
+}
+
+impl From<AdversaryKind> for AdversaryStrategyKind {
+    fn from(kind: AdversaryKind) -> Self {
+        match kind {
+            AdversaryKind::Random => AdversaryStrategyKind::Random,
+            AdversaryKind::WeakestResource => AdversaryStrategyKind::WeakestResource,
+            AdversaryKind::Escalating => AdversaryStrategyKind::Escalating,
+            AdversaryKind::Director => AdversaryStrategyKind::Director,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Monte Carlo balance simulator")]
+struct Args {
+    /// Directory with the JSON content, see `GameContent::load`
+    #[arg(long, default_value = "assets")]
+    assets: String,
+
+    /// Number of games to play per policy
+    #[arg(long, default_value_t = 1000)]
+    games: usize,
+
+    #[arg(long, value_enum, default_value_t = PolicyKind::All)]
+    policy: PolicyKind,
+
+    /// Difficulty preset from difficulty.json
+    #[arg(long, default_value = "normal")]
+    difficulty: String,
+
+    /// How the adversary picks its cards, defaults to the strategy of the difficulty
+    #[arg(long, value_enum)]
+    adversary: Option<AdversaryKind>,
+
+    /// Turns the lookahead policy searches ahead
+    #[arg(long, default_value_t = 1)]
+    depth: u32,
+
+    /// Games are cut short after this many turns
+    #[arg(long, default_value_t = 300)]
+    max_turns: u32,
+
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let content = GameContent::load(&args.assets)?;
+
+    let mut policies: Vec<Box<dyn Policy>> = Vec::new();
+    if matches!(args.policy, PolicyKind::Random | PolicyKind::All) {
+        policies.push(Box::new(RandomPolicy));
+    }
+    if matches!(args.policy, PolicyKind::Greedy | PolicyKind::All) {
+        policies.push(Box::new(GreedyPolicy));
+    }
+    if matches!(args.policy, PolicyKind::Lookahead | PolicyKind::All) {
+        policies.push(Box::new(LookaheadPolicy { depth: args.depth }));
+    }
+
+    let mut simulator = Simulator::new(&content);
+    simulator.max_turns = args.max_turns;
+    simulator.difficulty = content
+        .difficulty(&args.difficulty)
+        .with_context(|| format!("unknown difficulty {}", args.difficulty))?
+        .clone();
+    simulator.adversary = args.adversary.map(Into::into);
+
+    for policy in &mut policies {
+        let report = simulator.run(policy.as_mut(), args.games, args.seed);
+        println!("{report}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Checks the JSON content against the asset files and itself: card pictures and
+//! cutscenes, ending scripts and pictures, room objects and status effect names.
+//!
+//! ```sh
+//! cargo run --manifest-path crates/game_rules/Cargo.toml --bin validate-content
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use game_rules::{GameContent, validate_content};
+
+#[derive(Parser, Debug)]
+#[command(about = "Validate the game content")]
+struct Args {
+    /// Directory with the JSON content and the assets it refers to
+    #[arg(long, default_value = "assets")]
+    assets: PathBuf,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let args = Args::parse();
+    let content = GameContent::load(&args.assets)?;
+
+    let problems = validate_content(&content, &args.assets);
+    for problem in &problems {
+        println!("{problem}");
+    }
+
+    if problems.is_empty() {
+        println!("Content is valid");
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("{} problems found", problems.len());
+        Ok(ExitCode::FAILURE)
+    }
+}
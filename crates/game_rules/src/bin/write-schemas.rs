@@ -0,0 +1,52 @@
+//! Writes JSON Schemas of the content files to `assets/schemas`, so editors can complete
+//! and check `cards.json`, `schizophrenic-cards.json` and `endgame.json` while writing.
+//!
+//! ```sh
+//! cargo run --manifest-path crates/game_rules/Cargo.toml --features schema --bin write-schemas
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::Parser;
+use game_rules::{ActivityCard, EndGameScenario, SchizophrenicCard};
+use schemars::{JsonSchema, schema_for};
+
+#[derive(Parser, Debug)]
+#[command(about = "Write JSON Schemas of the content files")]
+struct Args {
+    /// Directory with the JSON content, the schemas go to its `schemas` directory
+    #[arg(long, default_value = "assets")]
+    assets: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let schemas_dir = args.assets.join("schemas");
+    fs::create_dir_all(&schemas_dir)
+        .with_context(|| format!("failed to create {}", schemas_dir.display()))?;
+
+    write_schema::<ActivityCard>(&schemas_dir, "cards", "Activity cards")?;
+    write_schema::<SchizophrenicCard>(
+        &schemas_dir,
+        "schizophrenic-cards",
+        "Schizophrenic adversary cards",
+    )?;
+    write_schema::<EndGameScenario>(&schemas_dir, "endgame", "Endgame scenarios")?;
+
+    Ok(())
+}
+
+/// Every content file is a list of `T`
+fn write_schema<T: JsonSchema>(schemas_dir: &Path, name: &str, title: &str) -> anyhow::Result<()> {
+    let mut schema = schema_for!(Vec<T>);
+    schema.insert("title".to_string(), title.into());
+
+    let path = schemas_dir.join(format!("{name}.schema.json"));
+    let text = serde_json::to_string_pretty(&schema)? + "\n";
+    fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+
+    Ok(())
+}
@@ -0,0 +1,341 @@
+use serde::{Deserialize, Serialize};
+
+use crate::condition::Condition;
+use crate::psych::{PsychEffects, PsychStat, PsychStats, StatThreshold};
+use crate::state::{AdversaryCardType, AdversaryConditions, AdversaryEffects};
+
+// Activity Card Types
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ActivityCard {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub conditions: CardConditions,
+    pub costs: CardCosts,
+    pub effects: ResourceEffects,
+    #[serde(default)]
+    pub psych_effects: PsychEffects,
+    pub status_effects: Vec<StatusEffectApplication>,
+    pub card_type: CardType,
+    pub availability: CardAvailability,
+    pub flavor_text: String,
+    pub one_time_use: bool,
+    pub cooldown: Option<u32>,
+    /// Longer explanation of what playing the card does, for writers and tooltips
+    #[serde(default)]
+    pub effect: String,
+    /// Prompt the card art was generated from
+    #[serde(default)]
+    pub prompt: String,
+}
+
+// Schizophrenic Card Types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SchizophrenicCard {
+    pub id: u32,
+    pub card_name: String,
+    pub scenario_id: String,
+    pub title: String,
+    /// Always `spectrum`, kept from the original card sheet
+    #[serde(default)]
+    pub card_type: String,
+    pub conditions: SpectrumConditions,
+    pub setup: String,
+    /// Prompt the scenario background was generated from
+    #[serde(default)]
+    pub image_prompt: String,
+    #[serde(default)]
+    pub adversary_type: AdversaryCardType,
+    #[serde(default)]
+    pub adversary_conditions: AdversaryConditions,
+    #[serde(default)]
+    pub effects: AdversaryEffects,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CardConditions {
+    pub min_sleep: Option<f32>,
+    pub min_health: Option<f32>,
+    pub min_mental: Option<f32>,
+    pub min_food: Option<f32>,
+    pub max_sleep: Option<f32>,
+    pub max_health: Option<f32>,
+    pub max_mental: Option<f32>,
+    pub max_food: Option<f32>,
+    pub required_mood: Option<Mood>,
+    pub forbidden_mood: Option<Mood>,
+    pub time_of_day: Option<Vec<TimeOfDay>>,
+    pub day_range: Option<(u32, u32)>,
+    pub required_objects: Option<Vec<String>>,
+    pub crisis_level: Option<CrisisLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CardCosts {
+    pub sleep_cost: f32,
+    pub health_cost: f32,
+    pub mental_cost: f32,
+    pub food_cost: f32,
+    pub time_cost: f32,
+    pub additional_costs: Vec<AdditionalCost>,
+}
+
+impl CardCosts {
+    pub fn resource_cost(&self, resource_type: ResourceType) -> f32 {
+        match resource_type {
+            ResourceType::Sleep => self.sleep_cost,
+            ResourceType::Health => self.health_cost,
+            ResourceType::Mental => self.mental_cost,
+            ResourceType::Food => self.food_cost,
+        }
+    }
+
+    /// Whether paying these costs takes anything from the resource
+    pub fn spends(&self, resource_type: ResourceType) -> bool {
+        self.resource_cost(resource_type) > 0.0
+            || self.additional_costs.iter().any(|cost| {
+                *cost
+                    == AdditionalCost::ConsumeAllOfResource {
+                        resource: resource_type,
+                    }
+            })
+    }
+}
+
+/// Costs beyond plain resource spending, unknown kinds fail when the cards are loaded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum AdditionalCost {
+    AddNegativeCard {
+        card: String,
+    },
+    LockResource {
+        resource: ResourceType,
+        turns: u32,
+        #[serde(default)]
+        kind: LockKind,
+    },
+    ConsumeAllOfResource {
+        resource: ResourceType,
+    },
+}
+
+/// What a resource lock holds in place
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LockKind {
+    /// The resource can't increase
+    #[default]
+    NoGain,
+    /// The player can't spend the resource, cards costing it can't be played, it still drains
+    NoSpend,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ResourceLock {
+    pub kind: LockKind,
+    pub turns: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResourceEffects {
+    pub sleep: f32,
+    pub health: f32,
+    pub mental: f32,
+    pub food: f32,
+}
+
+impl ResourceEffects {
+    pub fn resource_effect(&self, resource_type: ResourceType) -> f32 {
+        match resource_type {
+            ResourceType::Sleep => self.sleep,
+            ResourceType::Health => self.health,
+            ResourceType::Mental => self.mental,
+            ResourceType::Food => self.food,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatusEffectApplication {
+    pub effect: StatusEffect,
+    pub duration: u32,
+    pub intensity: f32,
+}
+
+// Conditions specific to spectrum cards
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpectrumConditions {
+    pub trigger_symptoms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reality_perception: Option<StatThreshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mental_state: Option<StatThreshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensory_sensitivity: Option<StatThreshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub social_connection: Option<StatThreshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_stability: Option<StatThreshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anxiety_level: Option<StatThreshold>,
+}
+
+impl SpectrumConditions {
+    pub fn thresholds(&self) -> impl Iterator<Item = (PsychStat, StatThreshold)> {
+        [
+            (PsychStat::RealityPerception, self.reality_perception),
+            (PsychStat::MentalState, self.mental_state),
+            (PsychStat::SensorySensitivity, self.sensory_sensitivity),
+            (PsychStat::SocialConnection, self.social_connection),
+            (PsychStat::IdentityStability, self.identity_stability),
+            (PsychStat::AnxietyLevel, self.anxiety_level),
+        ]
+        .into_iter()
+        .filter_map(|(stat, threshold)| threshold.map(|threshold| (stat, threshold)))
+    }
+
+    pub fn is_met(&self, stats: &PsychStats) -> bool {
+        self.thresholds()
+            .all(|(stat, threshold)| threshold.is_met(stats.get(stat)))
+    }
+
+    /// Draw weight of a card whose thresholds hold, grows the deeper the stats are past them
+    pub fn weight(&self, stats: &PsychStats) -> f32 {
+        1.0 + self
+            .thresholds()
+            .map(|(stat, threshold)| threshold.margin(stats.get(stat)).max(0.0) / 10.0)
+            .sum::<f32>()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CardType {
+    #[default]
+    BasicNeed,
+    Entertainment,
+    Social,
+    Crisis,
+    TimeSpecific,
+    Delusion,
+    LLMGenerated,
+    ThoughtCard,
+    MemoryCard,
+    ImpulseCard,
+    ComboCard,
+    Spectrum, // New type for schizophrenic cards
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CardAvailability {
+    #[default]
+    Always,
+    ConditionalOnly,
+    CrisisOnly,
+    LLMOnly,
+    OneTime,
+    DailyReset,
+}
+
+/// Identifies a status effect, what it does is defined in `status_effects.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum StatusEffect {
+    Insomnia,
+    Sick,
+    Motivated,
+    Overwhelmed,
+    Addicted,
+    Exhausted,
+    Anxious,
+    Depressed,
+    Manic,
+    Stable,
+    Focused,
+    Hungry,
+}
+
+impl StatusEffect {
+    pub const ALL: [StatusEffect; 12] = [
+        StatusEffect::Insomnia,
+        StatusEffect::Sick,
+        StatusEffect::Motivated,
+        StatusEffect::Overwhelmed,
+        StatusEffect::Addicted,
+        StatusEffect::Exhausted,
+        StatusEffect::Anxious,
+        StatusEffect::Depressed,
+        StatusEffect::Manic,
+        StatusEffect::Stable,
+        StatusEffect::Focused,
+        StatusEffect::Hungry,
+    ];
+
+    /// Whether content refers to this effect by `name`, the variant name in any case
+    pub fn matches_name(&self, name: &str) -> bool {
+        format!("{self:?}").eq_ignore_ascii_case(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Mood {
+    Depressed,
+    Anxious,
+    Tired,
+    Neutral,
+    Content,
+    Manic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TimeOfDay {
+    EarlyMorning,
+    Morning,
+    Afternoon,
+    Evening,
+    Night,
+    LateNight,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CrisisLevel {
+    None,
+    Mild,
+    Moderate,
+    Severe,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ResourceType {
+    Sleep,
+    Health,
+    Mental,
+    Food,
+}
+
+impl ResourceType {
+    pub const ALL: [ResourceType; 4] = [
+        ResourceType::Sleep,
+        ResourceType::Health,
+        ResourceType::Mental,
+        ResourceType::Food,
+    ];
+}
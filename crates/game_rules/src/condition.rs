@@ -0,0 +1,773 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{CardType, CrisisLevel, Mood, ResourceType, StatusEffect, TimeOfDay};
+use crate::history::Milestone;
+use crate::psych::PsychStat;
+use crate::state::GameState;
+
+/// A condition written as a small expression in the content, shared by cards, adversary
+/// cards, endings and cutscenes, e.g. `health <= 20 and (status(Depressed) or played(3) >= 2)`
+///
+/// - Booleans: `and`, `or`, `not` (or `&&`, `||`, `!`) and parentheses
+/// - Comparisons: `<`, `<=`, `>`, `>=`, `==`, `!=` over numbers, `+ - * / %` in between
+/// - Variables: `sleep`, `health`, `mental`, `food`, the hidden stats like `anxiety_level`,
+///   `day`, `hour`, `turn` (passed turns count too), `cards_played`, `stable_days`,
+///   `negative_cards`, and `mood`, `time`, `crisis` which compare to their variant names:
+///   `mood == Content`
+/// - Functions: `status(Effect)`, `symptom(name)`, `intensity(Effect)`,
+///   `played(card_id)`, `played(card_id, turns)` for the last turns only,
+///   `adversary_played(card_id)`, and from the [`PlayHistory`](crate::PlayHistory)
+///   `played_type(CardType)`, `streak(CardType)`, `longest_streak(CardType)`,
+///   `milestone(Milestone)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+impl Condition {
+    /// Whether the condition holds in `state` during turn `turn_number` of the game
+    pub fn evaluate(&self, state: &GameState, turn_number: u32) -> bool {
+        self.expr.truth(state, turn_number)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = || {
+            let mut parser = Parser {
+                tokens: tokenize(s)?,
+                next: 0,
+            };
+            let expr = parser.expression()?;
+            if let Some(token) = parser.peek() {
+                return Err(format!("Unexpected {token}"));
+            }
+            if expr.kind() != Kind::Bool {
+                return Err("Condition must be true or false, not a number".to_string());
+            }
+            Ok(expr)
+        };
+
+        let expr = parse().map_err(|err| format!("{err} in condition: {s}"))?;
+        Ok(Self {
+            source: s.trim().to_string(),
+            expr,
+        })
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Condition> for String {
+    fn from(condition: Condition) -> Self {
+        condition.source
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Bool,
+    Number,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+    Resource(ResourceType),
+    Psych(PsychStat),
+    Day,
+    Hour,
+    Turn,
+    CardsPlayed,
+    StableDays,
+    NegativeCards,
+    Mood,
+    Time,
+    Crisis,
+}
+
+impl Variable {
+    fn parse(name: &str) -> Option<Self> {
+        let variable = match name {
+            "sleep" => Variable::Resource(ResourceType::Sleep),
+            "health" => Variable::Resource(ResourceType::Health),
+            "mental" => Variable::Resource(ResourceType::Mental),
+            "food" => Variable::Resource(ResourceType::Food),
+            "day" => Variable::Day,
+            "hour" => Variable::Hour,
+            "turn" => Variable::Turn,
+            "cards_played" => Variable::CardsPlayed,
+            "stable_days" => Variable::StableDays,
+            "negative_cards" => Variable::NegativeCards,
+            "mood" => Variable::Mood,
+            "time" => Variable::Time,
+            "crisis" => Variable::Crisis,
+            _ => Variable::Psych(parse_name(name).ok()?),
+        };
+        Some(variable)
+    }
+
+    fn value(self, state: &GameState, turn_number: u32) -> f32 {
+        match self {
+            Variable::Resource(resource_type) => state.get_resource_value(resource_type),
+            Variable::Psych(stat) => state.psych_stats.get(stat),
+            Variable::Day => state.current_day as f32,
+            Variable::Hour => state.current_hour,
+            Variable::Turn => turn_number as f32,
+            Variable::CardsPlayed => state.action_history.len() as f32,
+            Variable::StableDays => state.consecutive_stable_days as f32,
+            Variable::NegativeCards => state.negative_card_count as f32,
+            Variable::Mood => state.current_mood as usize as f32,
+            Variable::Time => state.time_of_day as usize as f32,
+            Variable::Crisis => state.crisis_level as usize as f32,
+        }
+    }
+
+    /// Variant index of a name the variable compares to, for the enum-valued variables
+    fn variant(self, name: &str) -> Option<f32> {
+        let index = match self {
+            Variable::Mood => parse_name::<Mood>(name).ok()? as usize,
+            Variable::Time => parse_name::<TimeOfDay>(name).ok()? as usize,
+            Variable::Crisis => parse_name::<CrisisLevel>(name).ok()? as usize,
+            _ => return None,
+        };
+        Some(index as f32)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Function {
+    Status(StatusEffect),
+    Symptom(String),
+    Intensity(StatusEffect),
+    Played { card_id: u32, turns: Option<u32> },
+    AdversaryPlayed(u32),
+    PlayedType(CardType),
+    Streak(CardType),
+    LongestStreak(CardType),
+    Milestone(Milestone),
+}
+
+impl Function {
+    fn kind(&self) -> Kind {
+        match self {
+            Function::Status(_) | Function::Symptom(_) | Function::Milestone(_) => Kind::Bool,
+            Function::Intensity(_)
+            | Function::Played { .. }
+            | Function::AdversaryPlayed(_)
+            | Function::PlayedType(_)
+            | Function::Streak(_)
+            | Function::LongestStreak(_) => Kind::Number,
+        }
+    }
+
+    fn parse(name: &str, args: &[String]) -> Result<Self, String> {
+        let arity = |count: usize| {
+            if args.len() == count {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{name}() takes {count} argument(s), got {}",
+                    args.len()
+                ))
+            }
+        };
+        let number = |arg: &String| {
+            arg.parse::<u32>()
+                .map_err(|_| format!("{name}() expects a whole number, got {arg}"))
+        };
+
+        match name {
+            "status" | "intensity" => {
+                arity(1)?;
+                let effect = parse_name(&args[0])?;
+                Ok(if name == "status" {
+                    Function::Status(effect)
+                } else {
+                    Function::Intensity(effect)
+                })
+            }
+            "symptom" => {
+                arity(1)?;
+                Ok(Function::Symptom(args[0].clone()))
+            }
+            "played" => {
+                if args.len() == 2 {
+                    Ok(Function::Played {
+                        card_id: number(&args[0])?,
+                        turns: Some(number(&args[1])?),
+                    })
+                } else {
+                    arity(1)?;
+                    Ok(Function::Played {
+                        card_id: number(&args[0])?,
+                        turns: None,
+                    })
+                }
+            }
+            "adversary_played" => {
+                arity(1)?;
+                Ok(Function::AdversaryPlayed(number(&args[0])?))
+            }
+            "played_type" | "streak" | "longest_streak" => {
+                arity(1)?;
+                let card_type = parse_name(&args[0])?;
+                Ok(match name {
+                    "played_type" => Function::PlayedType(card_type),
+                    "streak" => Function::Streak(card_type),
+                    _ => Function::LongestStreak(card_type),
+                })
+            }
+            "milestone" => {
+                arity(1)?;
+                Ok(Function::Milestone(parse_name(&args[0])?))
+            }
+            _ => Err(format!("Unknown function {name}()")),
+        }
+    }
+
+    fn value(&self, state: &GameState, turn_number: u32) -> f32 {
+        match self {
+            Function::Status(effect) => bool_value(
+                state
+                    .status_effects
+                    .iter()
+                    .any(|status| status.effect == *effect),
+            ),
+            Function::Symptom(symptom) => {
+                bool_value(state.active_trigger_symptoms.contains(symptom))
+            }
+            Function::Intensity(effect) => state
+                .status_effects
+                .iter()
+                .filter(|status| status.effect == *effect)
+                .map(|status| status.intensity)
+                .sum(),
+            Function::Played { card_id, turns } => state
+                .action_history
+                .iter()
+                .filter(|action| action.card_id == Some(*card_id))
+                .filter(|action| turns.is_none_or(|turns| action.turn + turns > turn_number))
+                .count() as f32,
+            Function::AdversaryPlayed(card_id) => state
+                .adversary_actions
+                .iter()
+                .filter(|action| action.card_played.id == *card_id)
+                .count() as f32,
+            Function::PlayedType(card_type) => state.history.played(card_type) as f32,
+            Function::Streak(card_type) => state.history.current_streak(card_type) as f32,
+            Function::LongestStreak(card_type) => state.history.longest_streak(card_type) as f32,
+            Function::Milestone(milestone) => bool_value(state.history.has_reached(*milestone)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Or,
+    And,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl BinaryOp {
+    fn operand_kind(self) -> Kind {
+        match self {
+            BinaryOp::Or | BinaryOp::And => Kind::Bool,
+            _ => Kind::Number,
+        }
+    }
+
+    fn result_kind(self) -> Kind {
+        match self {
+            BinaryOp::Add
+            | BinaryOp::Subtract
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Remainder => Kind::Number,
+            _ => Kind::Bool,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f32),
+    Bool(bool),
+    Variable(Variable),
+    Function(Function),
+    Not(Box<Expr>),
+    Negate(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn kind(&self) -> Kind {
+        match self {
+            Expr::Number(_) | Expr::Variable(_) | Expr::Negate(_) => Kind::Number,
+            Expr::Bool(_) | Expr::Not(_) => Kind::Bool,
+            Expr::Function(function) => function.kind(),
+            Expr::Binary(op, _, _) => op.result_kind(),
+        }
+    }
+
+    fn truth(&self, state: &GameState, turn: u32) -> bool {
+        self.value(state, turn) != 0.0
+    }
+
+    /// Booleans evaluate to 1 and 0, the parser keeps the two kinds apart
+    fn value(&self, state: &GameState, turn: u32) -> f32 {
+        match self {
+            Expr::Number(number) => *number,
+            Expr::Bool(value) => bool_value(*value),
+            Expr::Variable(variable) => variable.value(state, turn),
+            Expr::Function(function) => function.value(state, turn),
+            Expr::Not(expr) => bool_value(!expr.truth(state, turn)),
+            Expr::Negate(expr) => -expr.value(state, turn),
+            Expr::Binary(BinaryOp::Or, left, right) => {
+                bool_value(left.truth(state, turn) || right.truth(state, turn))
+            }
+            Expr::Binary(BinaryOp::And, left, right) => {
+                bool_value(left.truth(state, turn) && right.truth(state, turn))
+            }
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.value(state, turn), right.value(state, turn));
+                match op {
+                    BinaryOp::Less => bool_value(left < right),
+                    BinaryOp::LessEqual => bool_value(left <= right),
+                    BinaryOp::Greater => bool_value(left > right),
+                    BinaryOp::GreaterEqual => bool_value(left >= right),
+                    BinaryOp::Equal => bool_value((left - right).abs() < f32::EPSILON),
+                    BinaryOp::NotEqual => bool_value((left - right).abs() >= f32::EPSILON),
+                    BinaryOp::Add => left + right,
+                    BinaryOp::Subtract => left - right,
+                    BinaryOp::Multiply => left * right,
+                    // Dividing by zero is false rather than infinite
+                    BinaryOp::Divide if right == 0.0 => 0.0,
+                    BinaryOp::Divide => left / right,
+                    BinaryOp::Remainder if right == 0.0 => 0.0,
+                    BinaryOp::Remainder => left % right,
+                    BinaryOp::Or | BinaryOp::And => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+fn bool_value(value: bool) -> f32 {
+    if value { 1.0 } else { 0.0 }
+}
+
+/// Parse a unit variant from its name, the way it is written in the JSON content
+fn parse_name<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("Unknown name {name}"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Name(String),
+    Text(String),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Name(name) => write!(f, "{name}"),
+            Token::Text(text) => write!(f, "\"{text}\""),
+            Token::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+/// Longest symbols first, so `<=` isn't read as `<`
+const SYMBOLS: [&str; 17] = [
+    "&&", "||", "<=", ">=", "==", "!=", "<", ">", "!", "(", ")", ",", "+", "-", "*", "/", "%",
+];
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = s.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| format!("Invalid number {}", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Name(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if c == '"' || c == '\'' {
+            let end = rest[1..]
+                .find(c)
+                .ok_or_else(|| "Unclosed quote".to_string())?;
+            tokens.push(Token::Text(rest[1..=end].to_string()));
+            rest = &rest[end + 2..];
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else {
+            return Err(format!("Unexpected '{c}'"));
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent, from the loosest binding `or` down to single values
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    /// Consume the next token if it is one of `symbols`, keywords included
+    fn eat(&mut self, symbols: &[&'static str]) -> Option<&'static str> {
+        let found = match self.peek()? {
+            Token::Symbol(symbol) => *symbol,
+            Token::Name(name) => match name.as_str() {
+                "and" => "and",
+                "or" => "or",
+                "not" => "not",
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if !symbols.contains(&found) {
+            return None;
+        }
+        self.next += 1;
+        Some(found)
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        match self.advance() {
+            Some(Token::Symbol(found)) if found == symbol => Ok(()),
+            Some(token) => Err(format!("Expected {symbol}, found {token}")),
+            None => Err(format!("Expected {symbol} at the end")),
+        }
+    }
+
+    fn binary(op: BinaryOp, left: Expr, right: Expr) -> Result<Expr, String> {
+        for operand in [&left, &right] {
+            if operand.kind() != op.operand_kind() {
+                return Err(format!("{op:?} can't be applied to {:?}", operand.kind()));
+            }
+        }
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat(&["or", "||"]).is_some() {
+            let right = self.and()?;
+            left = Self::binary(BinaryOp::Or, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.not()?;
+        while self.eat(&["and", "&&"]).is_some() {
+            let right = self.not()?;
+            left = Self::binary(BinaryOp::And, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat(&["not", "!"]).is_some() {
+            let expr = self.not()?;
+            if expr.kind() != Kind::Bool {
+                return Err("not can only be applied to a condition".to_string());
+            }
+            return Ok(Expr::Not(Box::new(expr)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.sum()?;
+        let Some(symbol) = self.eat(&["<", "<=", ">", ">=", "==", "!="]) else {
+            return Ok(left);
+        };
+        let op = match symbol {
+            "<" => BinaryOp::Less,
+            "<=" => BinaryOp::LessEqual,
+            ">" => BinaryOp::Greater,
+            ">=" => BinaryOp::GreaterEqual,
+            "==" => BinaryOp::Equal,
+            _ => BinaryOp::NotEqual,
+        };
+
+        // `mood == Content`, the variant name only means something next to its variable
+        let variant = match (&left, self.peek()) {
+            (Expr::Variable(variable), Some(Token::Name(name))) => variable.variant(name),
+            _ => None,
+        };
+        let right = match variant {
+            Some(index) => {
+                self.next += 1;
+                Expr::Number(index)
+            }
+            None => self.sum()?,
+        };
+        Self::binary(op, left, right)
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let mut left = self.product()?;
+        while let Some(symbol) = self.eat(&["+", "-"]) {
+            let op = if symbol == "+" {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Subtract
+            };
+            let right = self.product()?;
+            left = Self::binary(op, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some(symbol) = self.eat(&["*", "/", "%"]) {
+            let op = match symbol {
+                "*" => BinaryOp::Multiply,
+                "/" => BinaryOp::Divide,
+                _ => BinaryOp::Remainder,
+            };
+            let right = self.unary()?;
+            left = Self::binary(op, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat(&["-"]).is_some() {
+            let expr = self.unary()?;
+            if expr.kind() != Kind::Number {
+                return Err("- can only be applied to a number".to_string());
+            }
+            return Ok(Expr::Negate(Box::new(expr)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.advance() {
+            Some(Token::Number(number)) => Ok(Expr::Number(number)),
+            Some(Token::Symbol("(")) => {
+                let expr = self.expression()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Name(name)) if name == "true" => Ok(Expr::Bool(true)),
+            Some(Token::Name(name)) if name == "false" => Ok(Expr::Bool(false)),
+            Some(Token::Name(name)) if self.eat(&["("]).is_some() => {
+                let args = self.arguments()?;
+                Ok(Expr::Function(Function::parse(&name, &args)?))
+            }
+            Some(Token::Name(name)) => Variable::parse(&name)
+                .map(Expr::Variable)
+                .ok_or_else(|| format!("Unknown variable {name}")),
+            Some(token) => Err(format!("Unexpected {token}")),
+            None => Err("Condition ends too early".to_string()),
+        }
+    }
+
+    /// Function arguments are plain names, numbers or quoted text, read after the `(`
+    fn arguments(&mut self) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        if self.eat(&[")"]).is_some() {
+            return Ok(args);
+        }
+
+        loop {
+            match self.advance() {
+                Some(Token::Name(name) | Token::Text(name)) => args.push(name),
+                Some(Token::Number(number)) => args.push(number.to_string()),
+                Some(token) => return Err(format!("Unexpected {token} in arguments")),
+                None => return Err("Unclosed arguments".to_string()),
+            }
+            if self.eat(&[")"]).is_some() {
+                return Ok(args);
+            }
+            self.expect(",")?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::ActivityCard;
+    use crate::state::ActiveStatusEffect;
+
+    fn holds(condition: &str, state: &GameState, turn_number: u32) -> bool {
+        condition
+            .parse::<Condition>()
+            .unwrap()
+            .evaluate(state, turn_number)
+    }
+
+    fn parse_error(condition: &str) -> String {
+        condition.parse::<Condition>().unwrap_err()
+    }
+
+    #[test]
+    fn compares_resources_and_arithmetic() {
+        let state = GameState::default();
+        assert!(holds("health == 80", &state, 1));
+        assert!(holds("sleep + food > 100", &state, 1));
+        assert!(holds("health - 2 * 10 == 60", &state, 1));
+        assert!(holds("-food < 0", &state, 1));
+        assert!(!holds("mental >= 61", &state, 1));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let state = GameState::default();
+        assert!(holds("true or false and false", &state, 1));
+        assert!(!holds("(true or false) and false", &state, 1));
+        assert!(holds("not false && !(health < 10)", &state, 1));
+        assert!(holds("health > 10 || food > 1000", &state, 1));
+    }
+
+    #[test]
+    fn enum_variables_compare_to_variant_names() {
+        let state = GameState::default();
+        assert!(holds("mood == Neutral", &state, 1));
+        assert!(holds("time == Morning and crisis == None", &state, 1));
+        assert!(holds("mood != Manic", &state, 1));
+    }
+
+    #[test]
+    fn dividing_by_zero_is_zero() {
+        let state = GameState::default();
+        assert!(holds("health / 0 == 0", &state, 1));
+        assert!(holds("health % 0 == 0", &state, 1));
+    }
+
+    #[test]
+    fn turn_is_the_turn_of_the_game() {
+        let state = GameState::default();
+        assert!(holds("turn == 7", &state, 7));
+        assert!(holds("cards_played == 0", &state, 7));
+        assert!(!holds("turn % 10 == 0", &state, 7));
+        assert!(holds("turn % 10 == 0", &state, 20));
+    }
+
+    #[test]
+    fn played_counts_recent_turns_of_the_game() {
+        let mut state = GameState::default();
+        let card = ActivityCard {
+            id: 3,
+            ..Default::default()
+        };
+        state.apply_card_effects(&card, 2);
+        state.apply_card_effects(&card, 6);
+
+        assert!(holds("played(3) == 2", &state, 8));
+        assert!(holds("played(3, 3) == 1", &state, 8));
+        assert!(holds("played(3, 2) == 0", &state, 8));
+        assert!(holds("played(4) == 0", &state, 8));
+    }
+
+    #[test]
+    fn functions_read_effects_and_symptoms() {
+        let mut state = GameState::default();
+        state.status_effects.push(ActiveStatusEffect {
+            effect: StatusEffect::Depressed,
+            remaining_duration: 3,
+            intensity: 1.5,
+            source: String::new(),
+        });
+        state
+            .active_trigger_symptoms
+            .push("paranoid_delusions".to_string());
+
+        assert!(holds("status(Depressed) and not status(Manic)", &state, 1));
+        assert!(holds("intensity(Depressed) > 1", &state, 1));
+        assert!(holds("symptom(paranoid_delusions)", &state, 1));
+        assert!(holds("symptom('paranoid_delusions')", &state, 1));
+        assert!(!holds("milestone(StableWeek)", &state, 1));
+    }
+
+    #[test]
+    fn rejects_malformed_conditions() {
+        assert!(parse_error("health").contains("must be true or false"));
+        assert!(parse_error("health < 10 10").contains("Unexpected 10"));
+        assert!(parse_error("wealth > 10").contains("Unknown variable wealth"));
+        assert!(parse_error("status(Joyful)").contains("Unknown name Joyful"));
+        assert!(parse_error("played(1, 2, 3) > 0").contains("takes 1 argument(s)"));
+        assert!(parse_error("played(x) > 0").contains("expects a whole number"));
+        assert!(parse_error("health < 10 and 5").contains("can't be applied"));
+        assert!(parse_error("(health < 10").contains("Expected )"));
+        assert!(parse_error("symptom('open").contains("Unclosed quote"));
+        assert!(parse_error("health # 3").contains("Unexpected '#'"));
+        assert!(parse_error("").contains("ends too early"));
+    }
+
+    #[test]
+    fn round_trips_through_its_source() {
+        let condition: Condition = " health <= 20 and mood == Depressed ".parse().unwrap();
+        assert_eq!(condition.to_string(), "health <= 20 and mood == Depressed");
+
+        let json = serde_json::to_string(&condition).unwrap();
+        assert_eq!(json, "\"health <= 20 and mood == Depressed\"");
+        assert_eq!(serde_json::from_str::<Condition>(&json).unwrap(), condition);
+        assert!(serde_json::from_str::<Condition>("\"health\"").is_err());
+    }
+}
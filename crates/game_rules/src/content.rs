@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+
+use crate::cards::{ActivityCard, SchizophrenicCard};
+use crate::difficulty::Difficulty;
+use crate::endgame::EndGameScenario;
+use crate::status_effects::StatusEffectDefinitions;
+
+/// All JSON content the game is played with
+#[derive(Debug, Clone, Default)]
+pub struct GameContent {
+    pub activity_cards: Vec<ActivityCard>,
+    pub schizophrenic_cards: Vec<SchizophrenicCard>,
+    pub endgame_scenarios: Vec<EndGameScenario>,
+    pub difficulties: Vec<Difficulty>,
+    pub status_effects: StatusEffectDefinitions,
+}
+
+impl GameContent {
+    /// Load `cards.json`, `schizophrenic-cards.json`, `endgame.json`, `difficulty.json` and
+    /// `status_effects.json` from the assets directory
+    pub fn load(assets_dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let assets_dir = assets_dir.as_ref();
+
+        Ok(Self {
+            activity_cards: load_json(&assets_dir.join("cards.json"))?,
+            schizophrenic_cards: load_json(&assets_dir.join("schizophrenic-cards.json"))?,
+            endgame_scenarios: load_json(&assets_dir.join("endgame.json"))?,
+            difficulties: load_json(&assets_dir.join("difficulty.json"))?,
+            status_effects: load_json(&assets_dir.join("status_effects.json"))?,
+        })
+    }
+
+    pub fn difficulty(&self, id: &str) -> Option<&Difficulty> {
+        self.difficulties
+            .iter()
+            .find(|difficulty| difficulty.id == id)
+    }
+}
+
+fn load_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+}
@@ -0,0 +1,351 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{ActivityCard, CardAvailability, CardType};
+
+/// Cards the hand is filled up to at the start of every turn, it never holds more
+pub const HAND_SIZE: usize = 5;
+
+/// Credits earned for every stable day, spent between days on the deck
+pub const STABLE_DAY_CREDITS: u32 = 2;
+
+/// Credits a card offered between days costs
+pub const CARD_PRICE: u32 = 2;
+
+/// Credits it costs to take a card out of the deck for good
+pub const CARD_REMOVAL_PRICE: u32 = 1;
+
+/// Cards offered between days, drawn from the cards not in the deck
+pub const DECK_BUILDING_OFFER_SIZE: usize = 3;
+
+/// The deck never gets smaller than a full hand
+pub const MIN_DECK_SIZE: usize = HAND_SIZE;
+
+/// Mental health a mulligan costs, so redrawing a bad hand isn't free
+pub const MULLIGAN_MENTAL_COST: f32 = 5.0;
+
+/// Cards a run starts out with, basic needs and crises, everything else is bought between days
+pub fn starting_deck(cards: &[ActivityCard]) -> Vec<ActivityCard> {
+    cards
+        .iter()
+        .filter(|card| matches!(card.card_type, CardType::BasicNeed | CardType::Crisis))
+        .cloned()
+        .collect()
+}
+
+/// The player's cards for the run and the piles they cycle through
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Deck {
+    /// Cards still to be drawn, the last one is on top
+    pub draw_pile: Vec<ActivityCard>,
+    /// Drawn and not played yet, kept from turn to turn
+    pub hand: Vec<ActivityCard>,
+    /// Played cards, shuffled back under the draw pile once it runs out
+    pub discard_pile: Vec<ActivityCard>,
+    /// Played cards out of the cycle, one-time cards for the rest of the run and daily
+    /// cards until the day ends
+    pub exhausted: Vec<ActivityCard>,
+}
+
+impl Deck {
+    pub fn new(mut cards: Vec<ActivityCard>, rng: &mut impl Rng) -> Self {
+        cards.shuffle(rng);
+        Self {
+            draw_pile: cards,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draw_pile.is_empty()
+            && self.hand.is_empty()
+            && self.discard_pile.is_empty()
+            && self.exhausted.is_empty()
+    }
+
+    /// Every card of the deck, whichever pile it is in
+    pub fn cards(&self) -> impl Iterator<Item = &ActivityCard> {
+        self.draw_pile
+            .iter()
+            .chain(&self.hand)
+            .chain(&self.discard_pile)
+            .chain(&self.exhausted)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards().count()
+    }
+
+    pub fn contains(&self, card_id: u32) -> bool {
+        self.cards().any(|card| card.id == card_id)
+    }
+
+    /// A new card joins the cycle through the discard pile
+    pub fn add(&mut self, card: ActivityCard) {
+        self.discard_pile.push(card);
+    }
+
+    /// Take a card out of the deck for good, returns it and whether it was in the hand
+    pub fn remove(&mut self, card_id: u32) -> Option<(ActivityCard, bool)> {
+        for (pile, in_hand) in [
+            (&mut self.draw_pile, false),
+            (&mut self.hand, true),
+            (&mut self.discard_pile, false),
+            (&mut self.exhausted, false),
+        ] {
+            if let Some(index) = pile.iter().position(|card| card.id == card_id) {
+                return Some((pile.remove(index), in_hand));
+            }
+        }
+        None
+    }
+
+    /// Move the hand cards `discard` picks to the discard pile
+    pub fn discard(&mut self, discard: impl Fn(&ActivityCard) -> bool) -> Vec<ActivityCard> {
+        let (discarded, kept): (Vec<_>, Vec<_>) =
+            self.hand.drain(..).partition(|card| discard(card));
+        self.hand = kept;
+        self.discard_pile.extend(discarded.iter().cloned());
+        discarded
+    }
+
+    /// Fill the hand up to [`HAND_SIZE`] with the topmost `playable` cards, the discard pile is
+    /// shuffled back in once when the draw pile runs out of them. Only then are blocked cards
+    /// `dealable` picks dealt, so they never take the place of a card the player could use.
+    /// Returns the drawn cards and whether the discard pile was shuffled in.
+    pub fn draw(
+        &mut self,
+        playable: impl Fn(&ActivityCard) -> bool,
+        dealable: impl Fn(&ActivityCard) -> bool,
+        rng: &mut impl Rng,
+    ) -> (Vec<ActivityCard>, bool) {
+        let mut drawn = Vec::new();
+        let mut reshuffled = false;
+
+        while self.hand.len() < HAND_SIZE {
+            let index = match self.draw_pile.iter().rposition(&playable) {
+                Some(index) => Some(index),
+                None if !reshuffled && !self.discard_pile.is_empty() => {
+                    self.reshuffle(rng);
+                    reshuffled = true;
+                    continue;
+                }
+                None => self.draw_pile.iter().rposition(&dealable),
+            };
+            let Some(index) = index else {
+                break;
+            };
+
+            let card = self.draw_pile.remove(index);
+            self.hand.push(card.clone());
+            drawn.push(card);
+        }
+
+        (drawn, reshuffled)
+    }
+
+    fn reshuffle(&mut self, rng: &mut impl Rng) {
+        let mut pile = std::mem::take(&mut self.discard_pile);
+        pile.shuffle(rng);
+        pile.append(&mut self.draw_pile);
+        self.draw_pile = pile;
+    }
+
+    /// Take a played card out of the hand, one-time and daily cards are exhausted and the
+    /// others discarded
+    pub fn play(&mut self, card: &ActivityCard) {
+        let Some(index) = self.hand.iter().position(|held| held.id == card.id) else {
+            return;
+        };
+        let card = self.hand.remove(index);
+
+        if card.one_time_use
+            || matches!(
+                card.availability,
+                CardAvailability::OneTime | CardAvailability::DailyReset
+            )
+        {
+            self.exhausted.push(card);
+        } else {
+            self.discard_pile.push(card);
+        }
+    }
+
+    /// Daily cards come back into the cycle through the discard pile
+    pub fn end_day(&mut self) {
+        let (daily, exhausted): (Vec<_>, Vec<_>) = self.exhausted.drain(..).partition(|card| {
+            !card.one_time_use && card.availability == CardAvailability::DailyReset
+        });
+        self.exhausted = exhausted;
+        self.discard_pile.extend(daily);
+    }
+
+    /// Swap every card for its version in `cards` and drop the ones that are gone, for
+    /// content edited while the game runs
+    pub fn refresh(&mut self, cards: &[ActivityCard]) {
+        for pile in [
+            &mut self.draw_pile,
+            &mut self.hand,
+            &mut self.discard_pile,
+            &mut self.exhausted,
+        ] {
+            pile.retain_mut(|card| {
+                let Some(updated) = cards.iter().find(|updated| updated.id == card.id) else {
+                    return false;
+                };
+                *card = updated.clone();
+                true
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn card(id: u32, availability: CardAvailability) -> ActivityCard {
+        ActivityCard {
+            id,
+            availability,
+            ..Default::default()
+        }
+    }
+
+    fn deck(count: u32) -> Deck {
+        let cards = (1..=count).map(|id| card(id, CardAvailability::Always));
+        Deck::new(cards.collect(), &mut StdRng::seed_from_u64(0))
+    }
+
+    fn ids(cards: &[ActivityCard]) -> Vec<u32> {
+        cards.iter().map(|card| card.id).collect()
+    }
+
+    #[test]
+    fn draw_fills_the_hand_from_the_top() {
+        let mut deck = deck(8);
+        let top: Vec<u32> = ids(&deck.draw_pile)
+            .into_iter()
+            .rev()
+            .take(HAND_SIZE)
+            .collect();
+
+        let (drawn, reshuffled) = deck.draw(|_| true, |_| true, &mut StdRng::seed_from_u64(0));
+        assert_eq!(ids(&drawn), top);
+        assert_eq!(ids(&deck.hand), top);
+        assert!(!reshuffled);
+        assert_eq!(deck.draw_pile.len(), 3);
+
+        let (drawn, _) = deck.draw(|_| true, |_| true, &mut StdRng::seed_from_u64(0));
+        assert!(drawn.is_empty());
+    }
+
+    #[test]
+    fn discard_pile_is_shuffled_in_once_the_draw_pile_runs_out() {
+        let mut deck = deck(7);
+        deck.draw(|_| true, |_| true, &mut StdRng::seed_from_u64(0));
+        let discarded_ids = ids(&deck.hand[..3]);
+        let discarded = deck.discard(|card| discarded_ids.contains(&card.id));
+        assert_eq!(ids(&discarded), discarded_ids);
+        assert_eq!(deck.hand.len(), 2);
+
+        // Two cards left on the draw pile, the third comes from the reshuffled discards
+        let (drawn, reshuffled) = deck.draw(|_| true, |_| true, &mut StdRng::seed_from_u64(0));
+        assert!(reshuffled);
+        assert_eq!(drawn.len(), 3);
+        assert!(discarded_ids.contains(&drawn[2].id));
+        assert!(deck.discard_pile.is_empty());
+        assert_eq!(deck.draw_pile.len(), 2);
+    }
+
+    #[test]
+    fn draw_stops_when_every_card_is_held() {
+        let mut deck = deck(3);
+        let (drawn, reshuffled) = deck.draw(|_| true, |_| true, &mut StdRng::seed_from_u64(0));
+        assert_eq!(drawn.len(), 3);
+        assert!(!reshuffled);
+    }
+
+    #[test]
+    fn blocked_cards_are_dealt_only_once_no_playable_card_is_left() {
+        let mut deck = deck(8);
+        deck.discard_pile.push(card(9, CardAvailability::Always));
+
+        // Even ids are playable, 9 is shuffled in for them before blocked cards are dealt
+        // and 7 is never dealt at all
+        let (drawn, reshuffled) = deck.draw(
+            |card| card.id % 2 == 0 || card.id == 9,
+            |card| card.id != 7,
+            &mut StdRng::seed_from_u64(0),
+        );
+        assert!(reshuffled);
+        let mut held = ids(&drawn);
+        held.sort();
+        assert_eq!(held, [2, 4, 6, 8, 9]);
+
+        deck.hand.clear();
+        let (drawn, _) = deck.draw(
+            |_| false,
+            |card| card.id != 7,
+            &mut StdRng::seed_from_u64(0),
+        );
+        let mut held = ids(&drawn);
+        held.sort();
+        assert_eq!(held, [1, 3, 5]);
+    }
+
+    #[test]
+    fn one_time_and_daily_cards_are_exhausted() {
+        let mut deck = Deck {
+            hand: vec![
+                card(1, CardAvailability::Always),
+                card(2, CardAvailability::DailyReset),
+                card(3, CardAvailability::OneTime),
+            ],
+            ..Default::default()
+        };
+        for card in deck.hand.clone() {
+            deck.play(&card);
+        }
+        assert_eq!(ids(&deck.discard_pile), [1]);
+        assert_eq!(ids(&deck.exhausted), [2, 3]);
+
+        deck.end_day();
+        assert_eq!(ids(&deck.discard_pile), [1, 2]);
+        assert_eq!(ids(&deck.exhausted), [3]);
+    }
+
+    #[test]
+    fn playing_a_card_not_in_hand_changes_nothing() {
+        let mut deck = deck(6);
+        let outside = deck.draw_pile[0].clone();
+        deck.play(&outside);
+        assert_eq!(deck.draw_pile.len(), 6);
+        assert!(deck.discard_pile.is_empty());
+    }
+
+    #[test]
+    fn removed_cards_leave_every_pile() {
+        let mut deck = deck(6);
+        deck.draw(|_| true, |_| true, &mut StdRng::seed_from_u64(0));
+        let held = deck.hand[0].id;
+        let waiting = deck.draw_pile[0].id;
+
+        assert_eq!(
+            deck.remove(held).map(|(card, in_hand)| (card.id, in_hand)),
+            Some((held, true))
+        );
+        assert_eq!(
+            deck.remove(waiting).map(|(_, in_hand)| in_hand),
+            Some(false)
+        );
+        assert!(deck.remove(held).is_none());
+        assert_eq!(deck.len(), 4);
+        assert!(!deck.contains(held));
+    }
+}
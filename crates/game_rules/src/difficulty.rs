@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::adversary::AdversaryStrategyKind;
+use crate::cards::ResourceEffects;
+
+/// Rule modifiers of a difficulty preset, the presets live in `difficulty.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Difficulty {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Resources the character starts the game with
+    pub starting_resources: ResourceEffects,
+    /// Passive change of each resource at the end of every turn
+    pub turn_decay: ResourceEffects,
+    pub adversary_strategy: AdversaryStrategyKind,
+    /// Multiplier on the resource changes of adversary cards
+    pub adversary_aggression: f32,
+    /// Chance that a regular card shows its cutscene after the action
+    pub cutscene_chance: f32,
+    /// Stable days in a row that win the game
+    pub stable_days_to_win: u32,
+    /// Day on which the game is decided by the crisis level
+    pub day_limit: u32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self {
+            id: "normal".to_string(),
+            name: "Normal".to_string(),
+            description: "The game as it was meant to be played".to_string(),
+            starting_resources: ResourceEffects {
+                sleep: 70.0,
+                health: 80.0,
+                mental: 60.0,
+                food: 50.0,
+            },
+            turn_decay: ResourceEffects {
+                sleep: -5.0,
+                health: -2.5,
+                mental: -4.0,
+                food: -6.0,
+            },
+            adversary_strategy: AdversaryStrategyKind::Director,
+            adversary_aggression: 1.0,
+            cutscene_chance: 0.6,
+            stable_days_to_win: 7,
+            day_limit: 30,
+        }
+    }
+}
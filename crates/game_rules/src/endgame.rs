@@ -0,0 +1,410 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{CrisisLevel, Mood};
+use crate::condition::Condition;
+use crate::engine::Game;
+use crate::state::{EndGameResult, GameState};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EndGameScenario {
+    pub id: String,
+    pub title: String,
+    /// Checked before scenarios of a lower priority
+    #[serde(default)]
+    pub priority: i32,
+    /// Rule endings told by this scenario when no scenario's conditions hold, as long as
+    /// its `when` condition does, so the same ending can be told differently by how it was played
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub results: Vec<EndGameResult>,
+    pub trigger_conditions: TriggerConditions,
+    pub description: String,
+    pub image_prompt: String,
+    pub ending_text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TriggerConditions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mental_health: Option<ResourceCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<ResourceCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sleep: Option<ResourceCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub food: Option<ResourceCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crisis_level: Option<CrisisLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_effects_present: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mood: Option<Mood>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consecutive_stable_days: Option<ResourceCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_number: Option<ResourceCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_trigger_symptoms: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceCondition(pub u32, pub u32);
+
+impl<'de> Deserialize<'de> for ResourceCondition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, Visitor};
+        use std::fmt;
+
+        struct ResourceConditionVisitor;
+
+        impl<'de> Visitor<'de> for ResourceConditionVisitor {
+            type Value = ResourceCondition;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string condition like '<=0' or '30-50', or a number")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if let Some(threshold) = value.strip_prefix("<=") {
+                    let threshold: u32 = threshold.parse().map_err(de::Error::custom)?;
+                    Ok(ResourceCondition(0, threshold))
+                } else if let Some(threshold) = value.strip_prefix(">=") {
+                    let threshold: u32 = threshold.parse().map_err(de::Error::custom)?;
+                    Ok(ResourceCondition(threshold, 100))
+                } else if let Some(threshold) = value.strip_prefix('<') {
+                    let threshold: u32 = threshold.parse().map_err(de::Error::custom)?;
+                    Ok(ResourceCondition(0, threshold.saturating_sub(1)))
+                } else if let Some(threshold) = value.strip_prefix('>') {
+                    let threshold: u32 = threshold.parse().map_err(de::Error::custom)?;
+                    Ok(ResourceCondition(threshold + 1, 100))
+                } else if value.contains('-') {
+                    // Range condition like "30-50"
+                    let parts: Vec<&str> = value.split('-').collect();
+                    if parts.len() == 2 {
+                        let min: u32 = parts[0].trim().parse().map_err(de::Error::custom)?;
+                        let max: u32 = parts[1].trim().parse().map_err(de::Error::custom)?;
+                        Ok(ResourceCondition(min, max))
+                    } else {
+                        Err(de::Error::custom("Invalid range format"))
+                    }
+                } else {
+                    // Try to parse as a simple number
+                    let number: u32 = value.parse().map_err(de::Error::custom)?;
+                    Ok(ResourceCondition(number, number))
+                }
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let number = value as u32;
+                Ok(ResourceCondition(number, number))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let number = value as u32;
+                Ok(ResourceCondition(number, number))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let number = value as u32;
+                Ok(ResourceCondition(number, number))
+            }
+        }
+
+        deserializer.deserialize_any(ResourceConditionVisitor)
+    }
+}
+
+/// Same forms `Deserialize` accepts: a number, `"<=0"`, `">=70"`, `"<20"`, `">80"` or `"30-50"`
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for ResourceCondition {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ResourceCondition".into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A bound written as `\"<=0\"`, `\">=70\"`, `\"<20\"`, `\">80\"`, a range `\"30-50\"` or an exact number",
+            "anyOf": [
+                { "type": "string", "pattern": "^(<=|>=|<|>)?[0-9]+$|^[0-9]+ *- *[0-9]+$" },
+                { "type": "number", "minimum": 0 }
+            ]
+        })
+    }
+}
+
+impl ResourceCondition {
+    pub fn evaluate(&self, value: f32) -> bool {
+        let value_u32 = value as u32;
+        value_u32 >= self.0 && value_u32 <= self.1
+    }
+}
+
+impl TriggerConditions {
+    /// Nothing is checked, such a scenario is only reached through its `results`
+    pub fn is_empty(&self) -> bool {
+        self.mental_health.is_none()
+            && self.health.is_none()
+            && self.sleep.is_none()
+            && self.food.is_none()
+            && self.crisis_level.is_none()
+            && self.status_effects_present.is_none()
+            && self.mood.is_none()
+            && self.consecutive_stable_days.is_none()
+            && self.turn_number.is_none()
+            && self.active_trigger_symptoms.is_none()
+            && self.when.is_none()
+    }
+
+    pub fn evaluate(&self, game_state: &GameState, turn_number: u32) -> bool {
+        // Check mental health condition
+        if let Some(condition) = &self.mental_health
+            && !condition.evaluate(game_state.mental_health)
+        {
+            return false;
+        }
+
+        // Check health condition
+        if let Some(condition) = &self.health
+            && !condition.evaluate(game_state.health)
+        {
+            return false;
+        }
+
+        // Check sleep condition
+        if let Some(condition) = &self.sleep
+            && !condition.evaluate(game_state.sleep)
+        {
+            return false;
+        }
+
+        // Check food condition
+        if let Some(condition) = &self.food
+            && !condition.evaluate(game_state.food)
+        {
+            return false;
+        }
+
+        // Check crisis level
+        if let Some(required_crisis) = &self.crisis_level
+            && game_state.crisis_level != *required_crisis
+        {
+            return false;
+        }
+
+        // Check mood
+        if let Some(required_mood) = &self.mood
+            && game_state.current_mood != *required_mood
+        {
+            return false;
+        }
+
+        // Check consecutive stable days
+        if let Some(condition) = &self.consecutive_stable_days
+            && !condition.evaluate(game_state.consecutive_stable_days as f32)
+        {
+            return false;
+        }
+
+        if let Some(condition) = &self.turn_number
+            && !condition.evaluate(turn_number as f32)
+        {
+            return false;
+        }
+
+        // Check status effects
+        if let Some(ref required_effects) = self.status_effects_present {
+            for required_effect in required_effects {
+                let has_effect = game_state
+                    .status_effects
+                    .iter()
+                    .any(|status| status.effect.matches_name(required_effect));
+                if !has_effect {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(condition) = &self.when
+            && !condition.evaluate(game_state, turn_number)
+        {
+            return false;
+        }
+
+        // Check active trigger symptoms
+        if let Some(ref required_symptoms) = self.active_trigger_symptoms {
+            for required_symptom in required_symptoms {
+                if !game_state
+                    .active_trigger_symptoms
+                    .contains(required_symptom)
+                {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// How a run ended, the rules' verdict and the scenario that tells it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameOver {
+    pub result: Option<EndGameResult>,
+    pub scenario: Option<EndGameScenario>,
+}
+
+/// Highest priority scenario passing `filter`, the first one in the data wins ties
+fn top_scenario(
+    scenarios: &[EndGameScenario],
+    filter: impl Fn(&EndGameScenario) -> bool,
+) -> Option<&EndGameScenario> {
+    scenarios.iter().filter(|scenario| filter(scenario)).fold(
+        None,
+        |best: Option<&EndGameScenario>, scenario| match best {
+            Some(best) if best.priority >= scenario.priority => Some(best),
+            _ => Some(scenario),
+        },
+    )
+}
+
+/// Decide whether the game is over: scenarios whose conditions hold come first, by
+/// priority, then the ending the rules reached, told by the scenario written for it
+pub fn resolve_end_game(scenarios: &[EndGameScenario], game: &Game) -> Option<GameOver> {
+    let result = game.phase.game_over.clone();
+
+    let scenario = top_scenario(scenarios, |scenario| {
+        !scenario.trigger_conditions.is_empty()
+            && scenario
+                .trigger_conditions
+                .evaluate(&game.state, game.phase.turn_number)
+    })
+    .or_else(|| {
+        let result = result.as_ref()?;
+        top_scenario(scenarios, |scenario| {
+            scenario.results.contains(result)
+                && scenario
+                    .trigger_conditions
+                    .when
+                    .as_ref()
+                    .is_none_or(|condition| condition.evaluate(&game.state, game.phase.turn_number))
+        })
+    });
+
+    if result.is_none() && scenario.is_none() {
+        return None;
+    }
+
+    Some(GameOver {
+        result,
+        scenario: scenario.cloned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{FailureReason, SuccessReason};
+
+    fn scenario(id: &str, priority: i32, mut scenario: serde_json::Value) -> EndGameScenario {
+        for (key, value) in [
+            ("id", serde_json::json!(id)),
+            ("priority", serde_json::json!(priority)),
+            ("title", serde_json::json!(id)),
+            ("description", serde_json::json!("")),
+            ("image_prompt", serde_json::json!("")),
+            ("ending_text", serde_json::json!("")),
+        ] {
+            scenario[key] = value;
+        }
+        serde_json::from_value(scenario).unwrap()
+    }
+
+    #[test]
+    fn scenario_without_triggers_does_not_end_the_game() {
+        let scenarios = [scenario(
+            "fallback",
+            0,
+            serde_json::json!({
+                "results": [{ "Failure": "HealthCrisis" }],
+                "trigger_conditions": {}
+            }),
+        )];
+        let mut game = Game::new();
+        assert!(resolve_end_game(&scenarios, &game).is_none());
+
+        game.phase.game_over = Some(EndGameResult::Failure(FailureReason::HealthCrisis));
+        let game_over = resolve_end_game(&scenarios, &game).unwrap();
+        assert_eq!(game_over.scenario.unwrap().id, "fallback");
+    }
+
+    #[test]
+    fn highest_priority_scenario_whose_triggers_hold_wins() {
+        let scenarios = [
+            scenario(
+                "low",
+                1,
+                serde_json::json!({ "trigger_conditions": { "health": ">=50" } }),
+            ),
+            scenario(
+                "high",
+                5,
+                serde_json::json!({ "trigger_conditions": { "health": ">=50" } }),
+            ),
+            scenario(
+                "unmet",
+                9,
+                serde_json::json!({ "trigger_conditions": { "health": "<=10" } }),
+            ),
+        ];
+        let game = Game::new();
+
+        let game_over = resolve_end_game(&scenarios, &game).unwrap();
+        assert_eq!(game_over.scenario.unwrap().id, "high");
+        assert!(game_over.result.is_none());
+    }
+
+    #[test]
+    fn rule_ending_is_told_by_a_scenario_written_for_it() {
+        let scenarios = [
+            scenario(
+                "stability",
+                0,
+                serde_json::json!({
+                    "results": [{ "Success": "Stability" }],
+                    "trigger_conditions": { "health": "<=10" }
+                }),
+            ),
+            scenario(
+                "recovery",
+                0,
+                serde_json::json!({
+                    "results": [{ "Success": "Recovery" }],
+                    "trigger_conditions": { "health": "<=10" }
+                }),
+            ),
+        ];
+        let mut game = Game::new();
+        game.phase.game_over = Some(EndGameResult::Success(SuccessReason::Recovery));
+
+        let game_over = resolve_end_game(&scenarios, &game).unwrap();
+        assert_eq!(game_over.scenario.unwrap().id, "recovery");
+    }
+}
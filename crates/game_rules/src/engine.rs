@@ -0,0 +1,1237 @@
+use std::sync::LazyLock;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::cards::*;
+use crate::condition::Condition;
+use crate::deck::*;
+use crate::difficulty::Difficulty;
+use crate::state::*;
+
+/// Everything the player, the adversary or the presentation layer can do to the game
+#[derive(Debug, Clone)]
+pub enum GameAction {
+    /// The player starts cycling through a new deck of `cards`, shuffled from `shuffle_seed`
+    NewDeck {
+        cards: Vec<ActivityCard>,
+        shuffle_seed: u64,
+    },
+    /// The player hand is filled up from the deck, blocked cards only once no playable one is
+    /// left, `shuffle_seed` shuffles the discard pile back in when the draw pile runs out
+    DrawCards { shuffle_seed: u64 },
+    /// Once per turn while picking a card, the hand cards `card_ids` are discarded and the hand
+    /// filled up again for [`MULLIGAN_MENTAL_COST`] mental health
+    Mulligan {
+        card_ids: Vec<u32>,
+        shuffle_seed: u64,
+    },
+    /// Nothing in the hand can be played, the turn goes on without a card
+    PassTurn,
+    /// The player picked a card, `cutscene_roll` in `0.0..1.0` decides whether its cutscene plays
+    PlayCard {
+        card: ActivityCard,
+        cutscene_roll: f32,
+    },
+    /// The character finished the action of the played card
+    CompleteAction { card: ActivityCard },
+    /// The adversary hand was dealt
+    DrawAdversaryCards { count: usize },
+    /// The adversary picked a card from its hand
+    PlayAdversaryCard { card: SchizophrenicCard },
+    /// The running cutscene finished
+    EndCutscene,
+    /// The player ended the turn
+    EndTurn,
+    /// Once the shop opens between days, it is stocked from the `cards` not in the deck yet,
+    /// picked with `shuffle_seed`
+    StockShop {
+        cards: Vec<ActivityCard>,
+        shuffle_seed: u64,
+    },
+    /// Between days, the offered card is bought into the deck for [`CARD_PRICE`] credits
+    BuyCard { card_id: u32 },
+    /// Between days, the card is taken out of the deck for [`CARD_REMOVAL_PRICE`] credits
+    RemoveCard { card_id: u32 },
+    /// The player is done with the deck, the next turn starts
+    FinishDeckBuilding,
+}
+
+/// Everything that happened while applying a [`GameAction`]
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+    PhaseChanged {
+        new_phase: GamePhase,
+    },
+    TurnStarted {
+        turn_number: u32,
+    },
+    CardsDrawn {
+        cards: Vec<ActivityCard>,
+    },
+    /// Cards left the hand without being played
+    CardsDiscarded {
+        cards: Vec<ActivityCard>,
+    },
+    /// The discard pile was shuffled back under the draw pile
+    DeckReshuffled,
+    /// The shop opened with `offer` on sale
+    ShopStocked {
+        offer: Vec<ActivityCard>,
+    },
+    CardAddedToDeck {
+        card: ActivityCard,
+    },
+    CardRemovedFromDeck {
+        card: ActivityCard,
+    },
+    CardSelectionSucceeded {
+        card: ActivityCard,
+    },
+    CardSelectionFailed {
+        card: ActivityCard,
+        blocking_conditions: Vec<String>,
+    },
+    AdversaryCardPlayed {
+        card: SchizophrenicCard,
+    },
+    CutsceneStarted {
+        cutscene_id: String,
+        card_id: Option<u32>,
+        trigger_reason: CutsceneTrigger,
+    },
+    StatusEffectApplied {
+        effect: StatusEffect,
+        duration: u32,
+        source: String,
+    },
+    StatusEffectExpired {
+        effect: StatusEffect,
+    },
+    ResourceChanged {
+        resource_type: ResourceType,
+        old_value: f32,
+        new_value: f32,
+    },
+    MoodChanged {
+        old_mood: Mood,
+        new_mood: Mood,
+    },
+    TimeChanged {
+        old_hour: f32,
+        new_hour: f32,
+        old_time_of_day: TimeOfDay,
+        new_time_of_day: TimeOfDay,
+    },
+    DayChanged {
+        old_day: u32,
+        new_day: u32,
+    },
+    CrisisLevelChanged {
+        old_level: CrisisLevel,
+        new_level: CrisisLevel,
+    },
+    GameEnded {
+        result: EndGameResult,
+    },
+}
+
+/// Cutscenes checked when a turn ends, the first one whose condition holds plays
+const TURN_END_CUTSCENES: [(&str, &str); 6] = [
+    ("critical_health", "health < 10"),
+    ("mental_breakdown", "mental < 10"),
+    ("turn_milestone", "turn % 10 == 0"),
+    ("late_night_reflection", "hour >= 22 and time == Night"),
+    ("depression_cutscene", "mood == Depressed"),
+    ("manic_episode", "mood == Manic"),
+];
+
+/// [`TURN_END_CUTSCENES`] parsed once, on the first turn that ends
+static TURN_END_CONDITIONS: LazyLock<Vec<(&str, Condition)>> = LazyLock::new(|| {
+    TURN_END_CUTSCENES
+        .iter()
+        .map(|(cutscene_id, when)| {
+            let condition = when
+                .parse()
+                .unwrap_or_else(|err| panic!("turn end cutscene {cutscene_id}: {err}"));
+            (*cutscene_id, condition)
+        })
+        .collect()
+});
+
+/// Passage of time together with passive resource changes
+#[derive(Debug, Clone, Default)]
+pub struct GameStep {
+    pub time_delta: f32,
+    pub sleep_change: f32,
+    pub health_change: f32,
+    pub mental_health_change: f32,
+    pub food_change: f32,
+}
+
+/// Complete rules state, advanced only through [`Game::step`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Game {
+    pub state: GameState,
+    pub phase: GamePhaseState,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A fresh game played with the rule modifiers of `difficulty`
+    pub fn with_difficulty(difficulty: Difficulty) -> Self {
+        let mut game = Self::default();
+        for resource_type in ResourceType::ALL {
+            *game.state.resource_mut(resource_type) =
+                difficulty.starting_resources.resource_effect(resource_type);
+        }
+        game.state.current_mood = game.state.calculate_mood();
+        game.difficulty = difficulty;
+        game
+    }
+
+    /// Whether the card can be played right now, and what stands in the way if not
+    pub fn can_play_card(&self, card: &ActivityCard) -> (bool, Vec<String>) {
+        self.state.can_play_card(card, self.phase.turn_number)
+    }
+
+    pub fn is_adversary_card_eligible(&self, card: &SchizophrenicCard) -> bool {
+        card.adversary_conditions
+            .is_met(&self.state, self.phase.turn_number)
+            && card.conditions.is_met(&self.state.psych_stats)
+    }
+
+    /// Adversary cards that may be dealt right now
+    pub fn eligible_adversary_cards(&self, cards: &[SchizophrenicCard]) -> Vec<SchizophrenicCard> {
+        cards
+            .iter()
+            .filter(|card| self.is_adversary_card_eligible(card))
+            .cloned()
+            .collect()
+    }
+
+    /// Eligible adversary cards in draw order, cards whose thresholds the hidden stats are
+    /// furthest past tend to come first
+    pub fn deal_adversary_cards(
+        &self,
+        cards: &[SchizophrenicCard],
+        rng: &mut impl Rng,
+    ) -> Vec<SchizophrenicCard> {
+        // Weighted shuffle, each card is keyed by u^(1 / weight)
+        let mut keyed: Vec<(f32, SchizophrenicCard)> = self
+            .eligible_adversary_cards(cards)
+            .into_iter()
+            .map(|card| {
+                let weight = card.conditions.weight(&self.state.psych_stats);
+                (rng.random::<f32>().powf(1.0 / weight), card)
+            })
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        keyed.into_iter().map(|(_, card)| card).collect()
+    }
+
+    /// Apply a single action and return everything that happened as a result
+    pub fn step(&mut self, action: GameAction) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if self.phase.game_over.is_some() {
+            return events;
+        }
+
+        match action {
+            GameAction::NewDeck {
+                cards,
+                shuffle_seed,
+            } => {
+                self.state.deck = Deck::new(cards, &mut StdRng::seed_from_u64(shuffle_seed));
+            }
+            GameAction::DrawCards { shuffle_seed } => self.draw_cards(shuffle_seed, &mut events),
+            GameAction::Mulligan {
+                card_ids,
+                shuffle_seed,
+            } => self.mulligan(&card_ids, shuffle_seed, &mut events),
+            GameAction::PassTurn => self.pass_turn(&mut events),
+            GameAction::PlayCard {
+                card,
+                cutscene_roll,
+            } => self.play_card(card, cutscene_roll, &mut events),
+            GameAction::CompleteAction { card } => self.complete_action(&card, &mut events),
+            GameAction::DrawAdversaryCards { count } => {
+                self.draw_adversary_cards(count, &mut events)
+            }
+            GameAction::PlayAdversaryCard { card } => self.play_adversary_card(card, &mut events),
+            GameAction::EndCutscene => self.end_cutscene(&mut events),
+            GameAction::EndTurn => self.end_turn(&mut events),
+            GameAction::StockShop {
+                cards,
+                shuffle_seed,
+            } => self.stock_shop(cards, shuffle_seed, &mut events),
+            GameAction::BuyCard { card_id } => self.buy_card(card_id, &mut events),
+            GameAction::RemoveCard { card_id } => self.remove_card(card_id, &mut events),
+            GameAction::FinishDeckBuilding => {
+                if self.phase.current_phase == GamePhase::DeckBuilding {
+                    self.state.shop_offer = None;
+                    self.change_phase(GamePhase::CardDraw, &mut events);
+                }
+            }
+        }
+
+        events
+    }
+
+    fn draw_cards(&mut self, shuffle_seed: u64, events: &mut Vec<GameEvent>) {
+        if self.phase.cutscene_active || self.phase.current_phase != GamePhase::CardDraw {
+            return;
+        }
+
+        // Cards kept from the last turn that are still blocked make room for new ones
+        let blocked: Vec<u32> = self
+            .state
+            .deck
+            .hand
+            .iter()
+            .filter(|card| !self.can_play_card(card).0)
+            .map(|card| card.id)
+            .collect();
+        let drawn = self.refill_hand(&blocked, shuffle_seed, events);
+
+        self.phase.cards_drawn_count = drawn;
+        self.change_phase(GamePhase::CardSelection, events);
+    }
+
+    /// Whether the player may redraw part of the hand right now
+    pub fn can_mulligan(&self) -> bool {
+        !self.phase.cutscene_active
+            && self.phase.current_phase == GamePhase::CardSelection
+            && !self.phase.mulligan_used
+            && self.state.mental_health > MULLIGAN_MENTAL_COST
+            && self.state.spending_lock(ResourceType::Mental).is_none()
+    }
+
+    fn mulligan(&mut self, card_ids: &[u32], shuffle_seed: u64, events: &mut Vec<GameEvent>) {
+        if !self.can_mulligan() || card_ids.is_empty() {
+            return;
+        }
+
+        let hand = &self.state.deck.hand;
+        if !card_ids
+            .iter()
+            .all(|id| hand.iter().any(|card| card.id == *id))
+        {
+            return;
+        }
+
+        // Paid in full, a gain lock only holds back increases
+        self.phase.mulligan_used = true;
+        self.pass_time(
+            GameStep {
+                mental_health_change: -MULLIGAN_MENTAL_COST,
+                ..Default::default()
+            },
+            events,
+        );
+
+        let drawn = self.refill_hand(card_ids, shuffle_seed, events);
+        self.phase.cards_drawn_count += drawn;
+    }
+
+    fn pass_turn(&mut self, events: &mut Vec<GameEvent>) {
+        if self.phase.cutscene_active
+            || self.phase.current_phase != GamePhase::CardSelection
+            || self.has_playable_card()
+        {
+            return;
+        }
+
+        self.change_phase(GamePhase::AdversaryCardDraw, events);
+    }
+
+    /// Whether any card in the hand can be played right now
+    pub fn has_playable_card(&self) -> bool {
+        self.state
+            .deck
+            .hand
+            .iter()
+            .any(|card| self.can_play_card(card).0)
+    }
+
+    /// Discard the hand cards `card_ids` and fill the hand back up, returns how many were drawn
+    fn refill_hand(
+        &mut self,
+        card_ids: &[u32],
+        shuffle_seed: u64,
+        events: &mut Vec<GameEvent>,
+    ) -> usize {
+        let mut deck = std::mem::take(&mut self.state.deck);
+
+        let discarded = deck.discard(|card| card_ids.contains(&card.id));
+        if !discarded.is_empty() {
+            events.push(GameEvent::CardsDiscarded { cards: discarded });
+        }
+        // Blocked cards only fill what playable ones can't, and never ones locked to other
+        // hours, those would sit dead in the hand until they are discarded again
+        let time_of_day = self.state.time_of_day;
+        let (drawn, reshuffled) = deck.draw(
+            |card| self.can_play_card(card).0,
+            |card| {
+                card.conditions
+                    .time_of_day
+                    .as_ref()
+                    .is_none_or(|times| times.contains(&time_of_day))
+            },
+            &mut StdRng::seed_from_u64(shuffle_seed),
+        );
+        self.state.deck = deck;
+        if reshuffled {
+            events.push(GameEvent::DeckReshuffled);
+        }
+
+        let count = drawn.len();
+        events.push(GameEvent::CardsDrawn { cards: drawn });
+        count
+    }
+
+    fn play_card(&mut self, card: ActivityCard, cutscene_roll: f32, events: &mut Vec<GameEvent>) {
+        if self.phase.cutscene_active || self.phase.current_phase != GamePhase::CardSelection {
+            return;
+        }
+
+        // Only a card held in the hand can be played, and it plays as held whatever copy the
+        // caller has, a stale one after a reload or a forged one in a replay
+        let Some(card) = self
+            .state
+            .deck
+            .hand
+            .iter()
+            .find(|held| held.id == card.id)
+            .cloned()
+        else {
+            events.push(GameEvent::CardSelectionFailed {
+                card,
+                blocking_conditions: vec!["Not in hand".to_string()],
+            });
+            return;
+        };
+
+        let (can_play, blocking_conditions) = self.can_play_card(&card);
+        if !can_play {
+            events.push(GameEvent::CardSelectionFailed {
+                card,
+                blocking_conditions,
+            });
+            return;
+        }
+
+        events.push(GameEvent::CardSelectionSucceeded { card: card.clone() });
+
+        self.phase.selected_card_id = Some(card.id);
+
+        // Apply card effects first
+        events.extend(self.state.apply_card_effects(&card, self.phase.turn_number));
+        self.state.deck.play(&card);
+
+        // Check if cutscene for this card has already been shown
+        let cutscene_already_shown = self.state.shown_cutscenes.contains(&card.id);
+
+        // Check if cutscene should be triggered based on card type
+        let should_trigger_cutscene = !cutscene_already_shown
+            && match card.card_type {
+                CardType::Crisis | CardType::ComboCard => true, // Always show for these types
+                _ => cutscene_roll < self.difficulty.cutscene_chance,
+            };
+
+        if should_trigger_cutscene {
+            // Mark this cutscene as shown
+            self.state.shown_cutscenes.insert(card.id);
+
+            // Store cutscene to be triggered after action instead of triggering now
+            self.phase.pending_post_action_cutscene = Some(PendingCutscene {
+                cutscene_id: format!("{}", card.id),
+                card_id: Some(card.id),
+                trigger_reason: CutsceneTrigger::CardEffect,
+            });
+        }
+
+        // Always proceed to character action phase
+        self.change_phase(GamePhase::CharacterAction, events);
+    }
+
+    fn complete_action(&mut self, card: &ActivityCard, events: &mut Vec<GameEvent>) {
+        if self.phase.cutscene_active || self.phase.current_phase != GamePhase::CharacterAction {
+            return;
+        }
+
+        // Apply time cost from played card
+        self.pass_time(
+            GameStep {
+                time_delta: card.costs.time_cost * 3600.0, // Convert hours to seconds
+                ..Default::default()
+            },
+            events,
+        );
+
+        // Check if there's a pending cutscene to trigger after the action
+        if let Some(pending_cutscene) = self.phase.pending_post_action_cutscene.take() {
+            self.start_cutscene(pending_cutscene, events);
+        } else {
+            // No cutscene pending, proceed to adversary phase
+            self.change_phase(GamePhase::AdversaryCardDraw, events);
+        }
+    }
+
+    fn draw_adversary_cards(&mut self, count: usize, events: &mut Vec<GameEvent>) {
+        if self.phase.cutscene_active || self.phase.current_phase != GamePhase::AdversaryCardDraw {
+            return;
+        }
+
+        // Nothing the adversary could play this turn
+        if count == 0 {
+            self.change_phase(GamePhase::TurnOver, events);
+            return;
+        }
+
+        self.change_phase(GamePhase::AdversaryCardSelection, events);
+    }
+
+    fn play_adversary_card(&mut self, card: SchizophrenicCard, events: &mut Vec<GameEvent>) {
+        if self.phase.current_phase != GamePhase::AdversaryCardSelection
+            || !self.is_adversary_card_eligible(&card)
+        {
+            return;
+        }
+
+        self.state.clear_trigger_symptoms();
+
+        // Apply adversary card effects (including trigger symptoms)
+        events.extend(
+            self.state
+                .apply_adversary_card_effects(&card, self.difficulty.adversary_aggression),
+        );
+        self.update_crisis_level(events);
+
+        self.state.adversary_actions.push(AdversaryAction {
+            turn: self.phase.turn_number,
+            card_played: card.clone(),
+            effects_applied: card.effects.clone(),
+            timestamp: self.state.current_hour,
+        });
+
+        self.phase.selected_adversary_card = Some(card.clone());
+        events.push(GameEvent::AdversaryCardPlayed { card });
+
+        // Adversary cards have no cutscenes yet, proceed directly to turn over
+        self.change_phase(GamePhase::TurnOver, events);
+    }
+
+    fn end_cutscene(&mut self, events: &mut Vec<GameEvent>) {
+        if !self.phase.cutscene_active {
+            return;
+        }
+
+        self.phase.cutscene_active = false;
+        self.phase.pending_cutscene = None;
+        self.phase.previous_phase = None;
+
+        match self.phase.current_phase {
+            // If we just finished a post-action cutscene, proceed to adversary card draw
+            GamePhase::CharacterAction => self.change_phase(GamePhase::AdversaryCardDraw, events),
+            // If we just finished an adversary cutscene, proceed to turn over
+            GamePhase::AdversaryCardSelection => self.change_phase(GamePhase::TurnOver, events),
+            GamePhase::CardSelection => self.change_phase(GamePhase::CharacterAction, events),
+            _ => {}
+        }
+    }
+
+    fn end_turn(&mut self, events: &mut Vec<GameEvent>) {
+        if self.phase.cutscene_active {
+            return;
+        }
+
+        // Clear phase state from previous turn
+        self.phase.selected_card_id = None;
+        self.phase.cards_drawn_count = 0;
+        self.phase.mulligan_used = false;
+        self.phase.adversary_cards_drawn.clear();
+        self.phase.selected_adversary_card = None;
+
+        // Increment turn number
+        self.phase.turn_number += 1;
+        events.push(GameEvent::TurnStarted {
+            turn_number: self.phase.turn_number,
+        });
+
+        // Apply passive time and resource changes, 1 hour per turn
+        let decay = &self.difficulty.turn_decay;
+        self.pass_time(
+            GameStep {
+                time_delta: 3600.0,
+                sleep_change: decay.sleep,
+                health_change: decay.health,
+                mental_health_change: decay.mental,
+                food_change: decay.food,
+            },
+            events,
+        );
+        self.state.drift_psych_stats();
+
+        if std::mem::take(&mut self.phase.deck_building_due) {
+            self.change_phase(GamePhase::DeckBuilding, events);
+        } else {
+            self.change_phase(GamePhase::CardDraw, events);
+        }
+    }
+
+    /// Stocked once per visit, the offer stays put until the shop closes
+    fn stock_shop(
+        &mut self,
+        cards: Vec<ActivityCard>,
+        shuffle_seed: u64,
+        events: &mut Vec<GameEvent>,
+    ) {
+        if self.phase.current_phase != GamePhase::DeckBuilding || self.state.shop_offer.is_some() {
+            return;
+        }
+
+        let mut offer: Vec<ActivityCard> = cards
+            .into_iter()
+            .filter(|card| !self.state.deck.contains(card.id))
+            .collect();
+        offer.shuffle(&mut StdRng::seed_from_u64(shuffle_seed));
+        offer.truncate(DECK_BUILDING_OFFER_SIZE);
+
+        self.state.shop_offer = Some(offer.clone());
+        events.push(GameEvent::ShopStocked { offer });
+    }
+
+    fn buy_card(&mut self, card_id: u32, events: &mut Vec<GameEvent>) {
+        if self.phase.current_phase != GamePhase::DeckBuilding
+            || self.state.credits < CARD_PRICE
+            || self.state.deck.contains(card_id)
+        {
+            return;
+        }
+        let Some(offer) = self.state.shop_offer.as_mut() else {
+            return;
+        };
+        let Some(index) = offer.iter().position(|card| card.id == card_id) else {
+            return;
+        };
+
+        let card = offer.remove(index);
+        self.state.credits -= CARD_PRICE;
+        self.state.deck.add(card.clone());
+        events.push(GameEvent::CardAddedToDeck { card });
+    }
+
+    fn remove_card(&mut self, card_id: u32, events: &mut Vec<GameEvent>) {
+        if self.phase.current_phase != GamePhase::DeckBuilding
+            || self.state.credits < CARD_REMOVAL_PRICE
+            || self.state.deck.len() <= MIN_DECK_SIZE
+        {
+            return;
+        }
+
+        let Some((card, in_hand)) = self.state.deck.remove(card_id) else {
+            return;
+        };
+        self.state.credits -= CARD_REMOVAL_PRICE;
+        if in_hand {
+            events.push(GameEvent::CardsDiscarded {
+                cards: vec![card.clone()],
+            });
+        }
+        events.push(GameEvent::CardRemovedFromDeck { card });
+    }
+
+    fn change_phase(&mut self, new_phase: GamePhase, events: &mut Vec<GameEvent>) {
+        self.phase.current_phase = new_phase;
+        events.push(GameEvent::PhaseChanged { new_phase });
+
+        if new_phase == GamePhase::TurnOver {
+            // Effects drain their resources before running down, so their last turn counts
+            let status_step = self.state.status_effect_step();
+            self.pass_time(status_step, events);
+            events.extend(self.state.tick_status_effects());
+
+            if let Some(cutscene_id) = self.turn_end_cutscene() {
+                self.start_cutscene(
+                    PendingCutscene {
+                        cutscene_id,
+                        card_id: None,
+                        trigger_reason: CutsceneTrigger::TurnEnd,
+                    },
+                    events,
+                );
+            }
+        }
+    }
+
+    fn start_cutscene(&mut self, cutscene: PendingCutscene, events: &mut Vec<GameEvent>) {
+        self.phase.pending_cutscene = Some(cutscene.cutscene_id.clone());
+        self.phase.previous_phase = Some(self.phase.current_phase);
+        self.phase.cutscene_active = true;
+
+        events.push(GameEvent::CutsceneStarted {
+            cutscene_id: cutscene.cutscene_id,
+            card_id: cutscene.card_id,
+            trigger_reason: cutscene.trigger_reason,
+        });
+    }
+
+    fn turn_end_cutscene(&self) -> Option<String> {
+        TURN_END_CONDITIONS
+            .iter()
+            .find(|(_, when)| when.evaluate(&self.state, self.phase.turn_number))
+            .map(|(cutscene_id, _)| cutscene_id.to_string())
+    }
+
+    fn pass_time(&mut self, step: GameStep, events: &mut Vec<GameEvent>) {
+        let game_state = &mut self.state;
+
+        // Update time
+        let old_hour = game_state.current_hour;
+        let old_day = game_state.current_day;
+        let old_time_of_day = game_state.time_of_day;
+
+        game_state.current_hour += step.time_delta * game_state.time_speed * (1.0 / 3600.0);
+
+        // Handle day rollover
+        let day_changed = game_state.current_hour >= 24.0;
+        if day_changed {
+            game_state.current_hour -= 24.0;
+            game_state.current_day += 1;
+
+            events.push(GameEvent::DayChanged {
+                old_day,
+                new_day: game_state.current_day,
+            });
+        }
+
+        // Update time of day
+        game_state.time_of_day = GameState::calculate_time_of_day(game_state.current_hour);
+
+        // Send time changed event if anything changed
+        if old_hour != game_state.current_hour || old_time_of_day != game_state.time_of_day {
+            events.push(GameEvent::TimeChanged {
+                old_hour,
+                new_hour: game_state.current_hour,
+                old_time_of_day,
+                new_time_of_day: game_state.time_of_day,
+            });
+        }
+
+        // Update resources and send events for changes
+        let resource_changes = [
+            (ResourceType::Sleep, step.sleep_change),
+            (ResourceType::Health, step.health_change),
+            (ResourceType::Mental, step.mental_health_change),
+            (ResourceType::Food, step.food_change),
+        ];
+
+        let mut resources_changed = false;
+        for (resource_type, change) in resource_changes {
+            let old_value = game_state.get_resource_value(resource_type);
+            let new_value =
+                (old_value + game_state.allowed_change(resource_type, change)).clamp(0.0, 100.0);
+
+            if (old_value - new_value).abs() > f32::EPSILON {
+                match resource_type {
+                    ResourceType::Sleep => game_state.sleep = new_value,
+                    ResourceType::Health => game_state.health = new_value,
+                    ResourceType::Mental => game_state.mental_health = new_value,
+                    ResourceType::Food => game_state.food = new_value,
+                }
+
+                resources_changed = true;
+                events.push(GameEvent::ResourceChanged {
+                    resource_type,
+                    old_value,
+                    new_value,
+                });
+            }
+        }
+
+        // Update mood and send event if changed
+        let old_mood = game_state.current_mood;
+        game_state.current_mood = game_state.calculate_mood();
+
+        if old_mood != game_state.current_mood {
+            events.push(GameEvent::MoodChanged {
+                old_mood,
+                new_mood: game_state.current_mood,
+            });
+        }
+
+        if resources_changed {
+            self.update_crisis_level(events);
+        }
+
+        if day_changed {
+            self.daily_reset();
+        }
+
+        if resources_changed
+            && self.phase.game_over.is_none()
+            && let Some(result) = self.state.check_end_game_conditions(&self.difficulty)
+        {
+            self.phase.game_over = Some(result.clone());
+            events.push(GameEvent::GameEnded { result });
+        }
+    }
+
+    fn update_crisis_level(&mut self, events: &mut Vec<GameEvent>) {
+        let old_level = self.state.crisis_level;
+        let new_level = self.state.calculate_crisis_level();
+
+        self.state
+            .history
+            .record_crisis_level(new_level, self.state.current_day);
+        if old_level != new_level {
+            self.state.crisis_level = new_level;
+            events.push(GameEvent::CrisisLevelChanged {
+                old_level,
+                new_level,
+            });
+        }
+    }
+
+    fn daily_reset(&mut self) {
+        let game_state = &mut self.state;
+
+        // Reset daily used cards
+        game_state.daily_used_cards.clear();
+        game_state.deck.end_day();
+
+        // Check for consecutive stable days
+        let all_resources_stable = [
+            game_state.sleep,
+            game_state.health,
+            game_state.mental_health,
+            game_state.food,
+        ]
+        .iter()
+        .all(|&r| r >= 50.0);
+
+        if all_resources_stable && game_state.crisis_level == CrisisLevel::None {
+            game_state.consecutive_stable_days += 1;
+            game_state.credits += STABLE_DAY_CREDITS;
+        } else {
+            game_state.consecutive_stable_days = 0;
+        }
+
+        game_state.history.record_day_end(
+            game_state.current_day,
+            game_state.consecutive_stable_days,
+            [
+                game_state.sleep,
+                game_state.health,
+                game_state.mental_health,
+                game_state.food,
+            ],
+        );
+
+        self.phase.deck_building_due = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(id: u32) -> ActivityCard {
+        ActivityCard {
+            id,
+            name: format!("Card {id}"),
+            ..Default::default()
+        }
+    }
+
+    /// A game in card selection with a full hand dealt from `deck_size` plain cards
+    fn game_in_selection(deck_size: u32) -> Game {
+        let mut game = Game::new();
+        game.step(GameAction::NewDeck {
+            cards: (1..=deck_size).map(card).collect(),
+            shuffle_seed: 0,
+        });
+        game.step(GameAction::DrawCards { shuffle_seed: 0 });
+        game
+    }
+
+    fn phase_changes(events: &[GameEvent]) -> Vec<GamePhase> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::PhaseChanged { new_phase } => Some(*new_phase),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_turn_goes_through_every_phase() {
+        let mut game = game_in_selection(8);
+        assert_eq!(game.phase.current_phase, GamePhase::CardSelection);
+        assert_eq!(game.state.deck.hand.len(), HAND_SIZE);
+
+        let played = game.state.deck.hand[0].clone();
+        let events = game.step(GameAction::PlayCard {
+            card: played.clone(),
+            cutscene_roll: 1.0,
+        });
+        assert_eq!(phase_changes(&events), [GamePhase::CharacterAction]);
+
+        let events = game.step(GameAction::CompleteAction { card: played });
+        assert_eq!(phase_changes(&events), [GamePhase::AdversaryCardDraw]);
+
+        let events = game.step(GameAction::DrawAdversaryCards { count: 0 });
+        assert_eq!(phase_changes(&events), [GamePhase::TurnOver]);
+
+        let events = game.step(GameAction::EndTurn);
+        assert_eq!(phase_changes(&events), [GamePhase::CardDraw]);
+        assert_eq!(game.phase.turn_number, 2);
+    }
+
+    #[test]
+    fn actions_out_of_phase_are_ignored() {
+        let mut game = Game::new();
+        let events = game.step(GameAction::PlayCard {
+            card: card(1),
+            cutscene_roll: 1.0,
+        });
+        assert!(events.is_empty());
+        assert_eq!(game.phase.current_phase, GamePhase::CardDraw);
+
+        let mut game = game_in_selection(8);
+        assert!(
+            game.step(GameAction::DrawCards { shuffle_seed: 1 })
+                .is_empty()
+        );
+        assert!(game.step(GameAction::FinishDeckBuilding).is_empty());
+
+        let hour = game.state.current_hour;
+        let card = ActivityCard {
+            costs: CardCosts {
+                time_cost: 5.0,
+                ..Default::default()
+            },
+            ..game.state.deck.hand[0].clone()
+        };
+        assert!(game.step(GameAction::CompleteAction { card }).is_empty());
+        assert_eq!(game.phase.current_phase, GamePhase::CardSelection);
+        assert_eq!(game.state.current_hour, hour);
+    }
+
+    #[test]
+    fn nothing_happens_once_the_game_is_over() {
+        let mut game = game_in_selection(8);
+        game.phase.game_over = Some(EndGameResult::Failure(FailureReason::HealthCrisis));
+
+        let card = game.state.deck.hand[0].clone();
+        let events = game.step(GameAction::PlayCard {
+            card,
+            cutscene_roll: 1.0,
+        });
+        assert!(events.is_empty());
+        assert_eq!(game.phase.current_phase, GamePhase::CardSelection);
+    }
+
+    fn cutscenes(events: &[GameEvent]) -> Vec<&str> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::CutsceneStarted { cutscene_id, .. } => Some(cutscene_id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn turn_end_cutscene_conditions_parse() {
+        assert_eq!(TURN_END_CONDITIONS.len(), TURN_END_CUTSCENES.len());
+    }
+
+    #[test]
+    fn turn_milestone_plays_every_tenth_turn_passed_or_not() {
+        for (turn_number, expected) in [(10, vec!["turn_milestone"]), (11, vec![])] {
+            let mut game = game_in_selection(8);
+            game.phase.turn_number = turn_number;
+            for card in &mut game.state.deck.hand {
+                card.conditions.min_health = Some(1000.0);
+            }
+
+            game.step(GameAction::PassTurn);
+            let events = game.step(GameAction::DrawAdversaryCards { count: 0 });
+            assert_eq!(cutscenes(&events), expected, "turn {turn_number}");
+        }
+    }
+
+    #[test]
+    fn card_outside_the_hand_is_not_played() {
+        let mut game = game_in_selection(8);
+        let outside = game.state.deck.draw_pile[0].clone();
+        let food = game.state.food;
+
+        let events = game.step(GameAction::PlayCard {
+            card: ActivityCard {
+                effects: ResourceEffects {
+                    food: 20.0,
+                    ..Default::default()
+                },
+                ..outside
+            },
+            cutscene_roll: 1.0,
+        });
+
+        assert!(matches!(
+            events.as_slice(),
+            [GameEvent::CardSelectionFailed { .. }]
+        ));
+        assert_eq!(game.state.food, food);
+        assert!(game.state.action_history.is_empty());
+        assert_eq!(game.phase.current_phase, GamePhase::CardSelection);
+    }
+
+    #[test]
+    fn card_plays_as_held_whatever_copy_is_sent() {
+        let mut game = game_in_selection(8);
+        let held = game.state.deck.hand[0].clone();
+        let food = game.state.food;
+
+        let events = game.step(GameAction::PlayCard {
+            card: ActivityCard {
+                effects: ResourceEffects {
+                    food: 20.0,
+                    ..Default::default()
+                },
+                conditions: CardConditions {
+                    min_health: Some(1000.0),
+                    ..Default::default()
+                },
+                ..held.clone()
+            },
+            cutscene_roll: 1.0,
+        });
+
+        assert!(matches!(
+            &events[0],
+            GameEvent::CardSelectionSucceeded { card } if card.effects.food == 0.0
+        ));
+        assert_eq!(game.state.food, food);
+        assert_eq!(game.state.deck.discard_pile[0].effects.food, 0.0);
+    }
+
+    #[test]
+    fn played_cards_cycle_back_through_the_discard_pile() {
+        let mut game = game_in_selection(HAND_SIZE as u32);
+        let played = game.state.deck.hand[0].clone();
+        game.step(GameAction::PlayCard {
+            card: played.clone(),
+            cutscene_roll: 1.0,
+        });
+        assert_eq!(game.state.deck.hand.len(), HAND_SIZE - 1);
+        assert_eq!(game.state.deck.discard_pile[0].id, played.id);
+        let played_id = played.id;
+
+        game.step(GameAction::CompleteAction { card: played });
+        game.step(GameAction::DrawAdversaryCards { count: 0 });
+        game.step(GameAction::EndTurn);
+        let events = game.step(GameAction::DrawCards { shuffle_seed: 0 });
+
+        // The draw pile is empty, the played card comes back after the reshuffle
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, GameEvent::DeckReshuffled))
+        );
+        assert_eq!(game.state.deck.hand.len(), HAND_SIZE);
+        assert!(game.state.deck.hand.iter().any(|card| card.id == played_id));
+    }
+
+    /// A game between days with `credits` to spend and a deck of `deck_size` plain cards
+    fn game_in_shop(deck_size: u32, credits: u32) -> Game {
+        let mut game = game_in_selection(deck_size);
+        game.phase.current_phase = GamePhase::DeckBuilding;
+        game.state.credits = credits;
+        game
+    }
+
+    #[test]
+    fn shop_is_stocked_once_with_cards_not_in_the_deck() {
+        let mut game = game_in_shop(HAND_SIZE as u32, 0);
+        let events = game.step(GameAction::StockShop {
+            cards: (1..=20).map(card).collect(),
+            shuffle_seed: 3,
+        });
+        let offer = game.state.shop_offer.clone().unwrap();
+        assert!(
+            matches!(&events[..], [GameEvent::ShopStocked { offer: stocked }]
+            if stocked.len() == offer.len())
+        );
+        assert_eq!(offer.len(), DECK_BUILDING_OFFER_SIZE);
+        assert!(offer.iter().all(|card| !game.state.deck.contains(card.id)));
+
+        // Restocking an open shop would let the player roll until the offer suits them
+        let events = game.step(GameAction::StockShop {
+            cards: (1..=20).map(card).collect(),
+            shuffle_seed: 4,
+        });
+        assert!(events.is_empty());
+        let ids = |offer: &[ActivityCard]| offer.iter().map(|card| card.id).collect::<Vec<_>>();
+        assert_eq!(ids(game.state.shop_offer.as_ref().unwrap()), ids(&offer));
+    }
+
+    #[test]
+    fn only_offered_cards_can_be_bought() {
+        let mut game = game_in_shop(HAND_SIZE as u32, 10);
+        assert!(game.step(GameAction::BuyCard { card_id: 10 }).is_empty());
+
+        game.step(GameAction::StockShop {
+            cards: (1..=20).map(card).collect(),
+            shuffle_seed: 3,
+        });
+        let offer = game.state.shop_offer.clone().unwrap();
+        let not_offered = (6..=20)
+            .find(|id| offer.iter().all(|card| card.id != *id))
+            .unwrap();
+        assert!(
+            game.step(GameAction::BuyCard {
+                card_id: not_offered
+            })
+            .is_empty()
+        );
+        assert!(!game.state.deck.contains(not_offered));
+
+        let bought = offer[0].id;
+        let events = game.step(GameAction::BuyCard { card_id: bought });
+        assert!(matches!(&events[..], [GameEvent::CardAddedToDeck { card }] if card.id == bought));
+        assert!(game.state.deck.contains(bought));
+        assert_eq!(game.state.credits, 10 - CARD_PRICE);
+        assert!(
+            game.state
+                .shop_offer
+                .as_ref()
+                .unwrap()
+                .iter()
+                .all(|card| card.id != bought)
+        );
+    }
+
+    #[test]
+    fn shop_offer_survives_a_save_and_closes_with_the_shop() {
+        let mut game = game_in_shop(HAND_SIZE as u32, 10);
+        game.step(GameAction::StockShop {
+            cards: (1..=20).map(card).collect(),
+            shuffle_seed: 3,
+        });
+        let offer = game.state.shop_offer.clone().unwrap();
+
+        let saved = serde_json::to_string(&game.state).unwrap();
+        let loaded: GameState = serde_json::from_str(&saved).unwrap();
+        let loaded_ids: Vec<u32> = loaded
+            .shop_offer
+            .unwrap()
+            .iter()
+            .map(|card| card.id)
+            .collect();
+        assert_eq!(
+            loaded_ids,
+            offer.iter().map(|card| card.id).collect::<Vec<_>>()
+        );
+
+        game.step(GameAction::FinishDeckBuilding);
+        assert!(game.state.shop_offer.is_none());
+    }
+
+    fn hand_ids(game: &Game) -> Vec<u32> {
+        game.state.deck.hand.iter().map(|card| card.id).collect()
+    }
+
+    #[test]
+    fn mulligan_discards_only_cards_in_the_hand() {
+        let mut game = game_in_selection(12);
+        let hand = hand_ids(&game);
+        let not_in_hand = (1..=12).find(|id| !hand.contains(id)).unwrap();
+
+        assert!(
+            game.step(GameAction::Mulligan {
+                card_ids: vec![],
+                shuffle_seed: 0,
+            })
+            .is_empty()
+        );
+        assert!(
+            game.step(GameAction::Mulligan {
+                card_ids: vec![hand[0], not_in_hand],
+                shuffle_seed: 0,
+            })
+            .is_empty()
+        );
+        assert_eq!(hand_ids(&game), hand);
+        assert!(!game.phase.mulligan_used);
+
+        let mental = game.state.mental_health;
+        game.step(GameAction::Mulligan {
+            card_ids: vec![hand[0]],
+            shuffle_seed: 0,
+        });
+        assert!(game.phase.mulligan_used);
+        assert!(!hand_ids(&game).contains(&hand[0]));
+        assert_eq!(game.state.mental_health, mental - MULLIGAN_MENTAL_COST);
+    }
+
+    #[test]
+    fn mulligan_cost_holds_under_resource_locks() {
+        let mut game = game_in_selection(12);
+        let hand = hand_ids(&game);
+        game.state.locked_resources.insert(
+            ResourceType::Mental,
+            ResourceLock {
+                kind: LockKind::NoSpend,
+                turns: 2,
+            },
+        );
+        assert!(
+            game.step(GameAction::Mulligan {
+                card_ids: vec![hand[0]],
+                shuffle_seed: 0,
+            })
+            .is_empty()
+        );
+        assert_eq!(hand_ids(&game), hand);
+
+        game.state.locked_resources.insert(
+            ResourceType::Mental,
+            ResourceLock {
+                kind: LockKind::NoGain,
+                turns: 2,
+            },
+        );
+        let mental = game.state.mental_health;
+        game.step(GameAction::Mulligan {
+            card_ids: vec![hand[0]],
+            shuffle_seed: 0,
+        });
+        assert_eq!(game.state.mental_health, mental - MULLIGAN_MENTAL_COST);
+    }
+
+    #[test]
+    fn turn_is_passed_only_once_every_hand_card_is_blocked() {
+        let mut game = game_in_selection(8);
+        for card in game.state.deck.hand.iter_mut().skip(1) {
+            card.conditions.min_health = Some(1000.0);
+        }
+        assert!(game.has_playable_card());
+        assert!(game.step(GameAction::PassTurn).is_empty());
+
+        game.state.deck.hand[0].conditions.min_health = Some(1000.0);
+        assert!(!game.has_playable_card());
+        let events = game.step(GameAction::PassTurn);
+        assert_eq!(phase_changes(&events), [GamePhase::AdversaryCardDraw]);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{CardType, CrisisLevel};
+
+/// Days of stability that make a week of it
+const STABLE_WEEK_DAYS: u32 = 7;
+
+/// Every resource at least this high when a day ends makes a balanced day
+const BALANCED_DAY_MIN_RESOURCE: f32 = 60.0;
+
+/// Points of a run worth remembering, each is reached once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Milestone {
+    /// A severe crisis eased off completely
+    CrisisSurvived,
+    /// A day ended with every resource in good shape
+    BalancedDay,
+    StableWeek,
+}
+
+/// How the run was played, kept alongside the action history so endings can tell paths apart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayHistory {
+    pub played_by_type: HashMap<CardType, u32>,
+    /// Type of the last card and how many cards of it were played in a row
+    pub streak: Option<(CardType, u32)>,
+    pub longest_streaks: HashMap<CardType, u32>,
+    /// Day each milestone was first reached on
+    pub milestones: HashMap<Milestone, u32>,
+    /// Worst crisis since the player was last out of crisis
+    pub worst_crisis: Option<CrisisLevel>,
+}
+
+impl PlayHistory {
+    pub fn record_card(&mut self, card_type: &CardType) {
+        *self.played_by_type.entry(card_type.clone()).or_default() += 1;
+
+        let length = match &self.streak {
+            Some((streak_type, length)) if streak_type == card_type => length + 1,
+            _ => 1,
+        };
+        self.streak = Some((card_type.clone(), length));
+
+        let longest = self.longest_streaks.entry(card_type.clone()).or_default();
+        *longest = (*longest).max(length);
+    }
+
+    pub fn record_crisis_level(&mut self, level: CrisisLevel, day: u32) {
+        if level == CrisisLevel::None {
+            if self
+                .worst_crisis
+                .take()
+                .is_some_and(|worst| worst >= CrisisLevel::Severe)
+            {
+                self.reach(Milestone::CrisisSurvived, day);
+            }
+        } else {
+            self.worst_crisis = Some(self.worst_crisis.map_or(level, |worst| worst.max(level)));
+        }
+    }
+
+    pub fn record_day_end(&mut self, day: u32, consecutive_stable_days: u32, resources: [f32; 4]) {
+        if resources
+            .iter()
+            .all(|&resource| resource >= BALANCED_DAY_MIN_RESOURCE)
+        {
+            self.reach(Milestone::BalancedDay, day);
+        }
+        if consecutive_stable_days >= STABLE_WEEK_DAYS {
+            self.reach(Milestone::StableWeek, day);
+        }
+    }
+
+    fn reach(&mut self, milestone: Milestone, day: u32) {
+        self.milestones.entry(milestone).or_insert(day);
+    }
+
+    pub fn played(&self, card_type: &CardType) -> u32 {
+        self.played_by_type.get(card_type).copied().unwrap_or(0)
+    }
+
+    /// Cards of the type played in a row up to now, 0 if the last card was another type
+    pub fn current_streak(&self, card_type: &CardType) -> u32 {
+        match &self.streak {
+            Some((streak_type, length)) if streak_type == card_type => *length,
+            _ => 0,
+        }
+    }
+
+    pub fn longest_streak(&self, card_type: &CardType) -> u32 {
+        self.longest_streaks.get(card_type).copied().unwrap_or(0)
+    }
+
+    pub fn has_reached(&self, milestone: Milestone) -> bool {
+        self.milestones.contains_key(&milestone)
+    }
+}
@@ -0,0 +1,35 @@
+//! Rules of the game without any engine dependencies.
+//!
+//! The whole game is a [`Game`] advanced by [`Game::step`]: every [`GameAction`]
+//! returns the list of [`GameEvent`]s it caused, which the presentation layer is
+//! free to animate, log or ignore.
+
+mod adversary;
+mod cards;
+mod condition;
+mod content;
+mod deck;
+mod difficulty;
+mod endgame;
+mod engine;
+mod history;
+mod psych;
+mod simulation;
+mod state;
+mod status_effects;
+mod validation;
+
+pub use adversary::*;
+pub use cards::*;
+pub use condition::Condition;
+pub use content::GameContent;
+pub use deck::*;
+pub use difficulty::Difficulty;
+pub use endgame::*;
+pub use engine::{Game, GameAction, GameEvent, GameStep};
+pub use history::*;
+pub use psych::*;
+pub use simulation::*;
+pub use state::*;
+pub use status_effects::*;
+pub use validation::*;
@@ -0,0 +1,280 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Psychological stats the player never sees directly, they decide which adversary cards
+/// the illness can deal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PsychStat {
+    RealityPerception,
+    MentalState,
+    SensorySensitivity,
+    SocialConnection,
+    IdentityStability,
+    AnxietyLevel,
+}
+
+impl PsychStat {
+    pub const ALL: [PsychStat; 6] = [
+        PsychStat::RealityPerception,
+        PsychStat::MentalState,
+        PsychStat::SensorySensitivity,
+        PsychStat::SocialConnection,
+        PsychStat::IdentityStability,
+        PsychStat::AnxietyLevel,
+    ];
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PsychStats {
+    pub reality_perception: f32,
+    pub mental_state: f32,
+    pub sensory_sensitivity: f32,
+    pub social_connection: f32,
+    pub identity_stability: f32,
+    pub anxiety_level: f32,
+}
+
+/// A fresh character is clear of every threshold in the adversary cards on purpose, the
+/// adversary plays nothing until the stats drift past one, about twelve turns when nothing
+/// holds them back
+impl Default for PsychStats {
+    fn default() -> Self {
+        Self {
+            reality_perception: 55.0,
+            mental_state: 60.0,
+            sensory_sensitivity: 45.0,
+            social_connection: 35.0,
+            identity_stability: 60.0,
+            anxiety_level: 45.0,
+        }
+    }
+}
+
+impl PsychStats {
+    pub fn get(&self, stat: PsychStat) -> f32 {
+        match stat {
+            PsychStat::RealityPerception => self.reality_perception,
+            PsychStat::MentalState => self.mental_state,
+            PsychStat::SensorySensitivity => self.sensory_sensitivity,
+            PsychStat::SocialConnection => self.social_connection,
+            PsychStat::IdentityStability => self.identity_stability,
+            PsychStat::AnxietyLevel => self.anxiety_level,
+        }
+    }
+
+    pub fn get_mut(&mut self, stat: PsychStat) -> &mut f32 {
+        match stat {
+            PsychStat::RealityPerception => &mut self.reality_perception,
+            PsychStat::MentalState => &mut self.mental_state,
+            PsychStat::SensorySensitivity => &mut self.sensory_sensitivity,
+            PsychStat::SocialConnection => &mut self.social_connection,
+            PsychStat::IdentityStability => &mut self.identity_stability,
+            PsychStat::AnxietyLevel => &mut self.anxiety_level,
+        }
+    }
+
+    /// Stats share the 0..100 range of the visible resources
+    pub fn change(&mut self, stat: PsychStat, delta: f32) {
+        let value = self.get_mut(stat);
+        *value = (*value + delta).clamp(0.0, 100.0);
+    }
+
+    pub fn apply(&mut self, effects: &PsychEffects) {
+        for stat in PsychStat::ALL {
+            self.change(stat, effects.get(stat));
+        }
+    }
+}
+
+/// How an activity card shifts the hidden stats
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PsychEffects {
+    pub reality_perception: f32,
+    pub mental_state: f32,
+    pub sensory_sensitivity: f32,
+    pub social_connection: f32,
+    pub identity_stability: f32,
+    pub anxiety_level: f32,
+}
+
+impl PsychEffects {
+    pub fn get(&self, stat: PsychStat) -> f32 {
+        match stat {
+            PsychStat::RealityPerception => self.reality_perception,
+            PsychStat::MentalState => self.mental_state,
+            PsychStat::SensorySensitivity => self.sensory_sensitivity,
+            PsychStat::SocialConnection => self.social_connection,
+            PsychStat::IdentityStability => self.identity_stability,
+            PsychStat::AnxietyLevel => self.anxiety_level,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    AtMost,
+    AtLeast,
+}
+
+/// A bound on a hidden stat, written as `"<=30"` or `">=70"` in the card data
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+pub struct StatThreshold {
+    pub comparison: Comparison,
+    pub value: f32,
+}
+
+impl StatThreshold {
+    pub fn is_met(&self, stat_value: f32) -> bool {
+        self.margin(stat_value) >= 0.0
+    }
+
+    /// How far past the threshold the stat is, negative while it is not reached
+    pub fn margin(&self, stat_value: f32) -> f32 {
+        match self.comparison {
+            Comparison::AtMost => self.value - stat_value,
+            Comparison::AtLeast => stat_value - self.value,
+        }
+    }
+
+    /// Change that pushes a stat further past the threshold
+    pub fn deepen(&self, amount: f32) -> f32 {
+        match self.comparison {
+            Comparison::AtMost => -amount,
+            Comparison::AtLeast => amount,
+        }
+    }
+}
+
+impl TryFrom<String> for StatThreshold {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let s = s.trim();
+        let (comparison, value) = if let Some(value) = s.strip_prefix("<=") {
+            (Comparison::AtMost, value)
+        } else if let Some(value) = s.strip_prefix(">=") {
+            (Comparison::AtLeast, value)
+        } else {
+            return Err(format!("Threshold must start with <= or >=: {s}"));
+        };
+
+        let value = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid threshold value: {s}"))?;
+        Ok(Self { comparison, value })
+    }
+}
+
+impl From<StatThreshold> for String {
+    fn from(threshold: StatThreshold) -> Self {
+        threshold.to_string()
+    }
+}
+
+impl fmt::Display for StatThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.comparison {
+            Comparison::AtMost => write!(f, "<={}", self.value),
+            Comparison::AtLeast => write!(f, ">={}", self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::SpectrumConditions;
+
+    fn threshold(s: &str) -> StatThreshold {
+        StatThreshold::try_from(s.to_string()).unwrap()
+    }
+
+    #[test]
+    fn thresholds_parse_both_comparisons() {
+        assert_eq!(
+            threshold("<=30"),
+            StatThreshold {
+                comparison: Comparison::AtMost,
+                value: 30.0,
+            }
+        );
+        assert_eq!(
+            threshold(" >= 70.5 "),
+            StatThreshold {
+                comparison: Comparison::AtLeast,
+                value: 70.5,
+            }
+        );
+        assert_eq!(threshold(">=70").to_string(), ">=70");
+
+        for invalid in ["30", "<30", "=>70", "<=", ">=high"] {
+            assert!(
+                StatThreshold::try_from(invalid.to_string()).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn thresholds_hold_on_the_bound() {
+        let at_most = threshold("<=30");
+        assert!(at_most.is_met(30.0));
+        assert!(at_most.is_met(10.0));
+        assert!(!at_most.is_met(30.5));
+        assert_eq!(at_most.margin(10.0), 20.0);
+
+        let at_least = threshold(">=70");
+        assert!(at_least.is_met(70.0));
+        assert!(!at_least.is_met(69.0));
+        assert_eq!(at_least.margin(60.0), -10.0);
+    }
+
+    #[test]
+    fn weight_grows_with_how_far_past_the_thresholds_the_stats_are() {
+        let conditions = SpectrumConditions {
+            reality_perception: Some(threshold("<=30")),
+            sensory_sensitivity: Some(threshold(">=70")),
+            ..Default::default()
+        };
+        let mut stats = PsychStats {
+            reality_perception: 30.0,
+            sensory_sensitivity: 70.0,
+            ..Default::default()
+        };
+        assert!(conditions.is_met(&stats));
+        assert_eq!(conditions.weight(&stats), 1.0);
+
+        stats.reality_perception = 10.0;
+        stats.sensory_sensitivity = 80.0;
+        assert_eq!(conditions.weight(&stats), 1.0 + 2.0 + 1.0);
+
+        // A threshold that isn't reached adds nothing rather than taking weight away
+        stats.sensory_sensitivity = 20.0;
+        assert!(!conditions.is_met(&stats));
+        assert_eq!(conditions.weight(&stats), 1.0 + 2.0);
+    }
+
+    #[test]
+    fn thresholds_round_trip_through_card_data() {
+        let conditions: SpectrumConditions = serde_json::from_value(serde_json::json!({
+            "trigger_symptoms": [],
+            "mental_state": "<=40",
+            "anxiety_level": ">=80"
+        }))
+        .unwrap();
+        assert_eq!(conditions.mental_state, Some(threshold("<=40")));
+        assert_eq!(conditions.anxiety_level, Some(threshold(">=80")));
+        assert_eq!(conditions.reality_perception, None);
+
+        let json = serde_json::to_value(&conditions).unwrap();
+        assert_eq!(json["mental_state"], "<=40");
+        assert!(json.get("reality_perception").is_none());
+    }
+}
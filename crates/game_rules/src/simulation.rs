@@ -0,0 +1,507 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::adversary::AdversaryStrategyKind;
+use crate::cards::*;
+use crate::content::GameContent;
+use crate::deck::{CARD_PRICE, HAND_SIZE, starting_deck};
+use crate::difficulty::Difficulty;
+use crate::endgame::resolve_end_game;
+use crate::engine::{Game, GameAction, GameEvent};
+use crate::state::*;
+
+/// The adversary is dealt one card more than the player
+pub const ADVERSARY_HAND_SIZE: usize = HAND_SIZE + 1;
+
+/// Decides which card the simulated player plays from the dealt hand
+pub trait Policy {
+    fn name(&self) -> String;
+
+    /// Index into `hand` of the card to play, `hand` holds the playable cards of the hand and
+    /// is never empty
+    fn choose_card(&mut self, game: &Game, hand: &[ActivityCard], rng: &mut StdRng) -> usize;
+}
+
+/// Plays any card from the hand
+pub struct RandomPolicy;
+
+impl Policy for RandomPolicy {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn choose_card(&mut self, _game: &Game, hand: &[ActivityCard], rng: &mut StdRng) -> usize {
+        rng.random_range(0..hand.len())
+    }
+}
+
+/// Plays the card that leaves the best state right after the action
+pub struct GreedyPolicy;
+
+impl Policy for GreedyPolicy {
+    fn name(&self) -> String {
+        "greedy".to_string()
+    }
+
+    fn choose_card(&mut self, game: &Game, hand: &[ActivityCard], _rng: &mut StdRng) -> usize {
+        best_card(hand, |card| {
+            let mut game = game.clone();
+            let events = play_through(&mut game, card);
+            score(&game, &events)
+        })
+    }
+}
+
+/// Plays the card with the best outcome a few turns ahead, over the hands the deck
+/// would deal in the following turns
+pub struct LookaheadPolicy {
+    pub depth: u32,
+}
+
+impl LookaheadPolicy {
+    fn value(&self, game: &Game, card: &ActivityCard, depth: u32) -> f32 {
+        let mut game = game.clone();
+        let mut events = play_through(&mut game, card);
+        events.extend(game.step(GameAction::EndTurn));
+        events.extend(game.step(GameAction::FinishDeckBuilding));
+
+        if depth == 0 || ended(&events) {
+            return score(&game, &events);
+        }
+
+        game.step(GameAction::DrawCards { shuffle_seed: 0 });
+        game.state
+            .deck
+            .hand
+            .iter()
+            .filter(|card| game.can_play_card(card).0)
+            .map(|card| self.value(&game, card, depth - 1))
+            .fold(None, |best: Option<f32>, value| {
+                Some(best.map_or(value, |best| best.max(value)))
+            })
+            .unwrap_or_else(|| score(&game, &events))
+    }
+}
+
+impl Policy for LookaheadPolicy {
+    fn name(&self) -> String {
+        format!("lookahead({})", self.depth)
+    }
+
+    fn choose_card(&mut self, game: &Game, hand: &[ActivityCard], _rng: &mut StdRng) -> usize {
+        best_card(hand, |card| self.value(game, card, self.depth))
+    }
+}
+
+fn best_card(hand: &[ActivityCard], mut value: impl FnMut(&ActivityCard) -> f32) -> usize {
+    hand.iter()
+        .enumerate()
+        .map(|(index, card)| (index, value(card)))
+        .fold((0, f32::NEG_INFINITY), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// Play a card and finish its action, skipping the cutscene
+fn play_through(game: &mut Game, card: &ActivityCard) -> Vec<GameEvent> {
+    let mut events = game.step(GameAction::PlayCard {
+        card: card.clone(),
+        cutscene_roll: 1.0,
+    });
+    events.extend(game.step(GameAction::CompleteAction { card: card.clone() }));
+    events.extend(game.step(GameAction::EndCutscene));
+    events
+}
+
+fn ended(events: &[GameEvent]) -> bool {
+    events
+        .iter()
+        .any(|event| matches!(event, GameEvent::GameEnded { .. }))
+}
+
+/// Heuristic value of a state, balanced resources are worth more than a single high one
+fn score(game: &Game, events: &[GameEvent]) -> f32 {
+    for event in events {
+        if let GameEvent::GameEnded { result } = event {
+            return match result {
+                EndGameResult::Success(_) => 1000.0,
+                EndGameResult::Failure(_) => -1000.0,
+            };
+        }
+    }
+
+    let state = &game.state;
+    let resources = [state.sleep, state.health, state.mental_health, state.food];
+    let total: f32 = resources.iter().sum();
+    let lowest = resources.iter().copied().fold(f32::INFINITY, f32::min);
+
+    total + 2.0 * lowest - 10.0 * state.crisis_level as u8 as f32
+}
+
+/// How a single simulated game went
+#[derive(Debug, Clone, Default)]
+pub struct GameRecord {
+    pub turns: u32,
+    pub result: Option<EndGameResult>,
+    pub scenario: Option<String>,
+    /// The deck ran out of cards, the real game would be stuck here
+    pub stalled: bool,
+    /// Turns where nothing in the hand could be played, even after a mulligan
+    pub passed_turns: u32,
+    pub cards_offered: Vec<u32>,
+    /// Card id and the change of all resources combined caused by playing it
+    pub cards_played: Vec<(u32, f32)>,
+}
+
+impl GameRecord {
+    pub fn is_win(&self) -> bool {
+        matches!(self.result, Some(EndGameResult::Success(_)))
+    }
+}
+
+/// Plays complete games against real content without any engine
+pub struct Simulator<'a> {
+    pub content: &'a GameContent,
+    pub max_turns: u32,
+    pub difficulty: Difficulty,
+    /// Plays the adversary with this strategy instead of the one of the difficulty
+    pub adversary: Option<AdversaryStrategyKind>,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(content: &'a GameContent) -> Self {
+        Self {
+            content,
+            max_turns: 300,
+            difficulty: Difficulty::default(),
+            adversary: None,
+        }
+    }
+
+    pub fn play(&self, policy: &mut dyn Policy, rng: &mut StdRng) -> GameRecord {
+        let mut game = Game::with_difficulty(self.difficulty.clone());
+        game.state.status_effect_definitions = self.content.status_effects.clone();
+        game.step(GameAction::NewDeck {
+            cards: starting_deck(&self.content.activity_cards),
+            shuffle_seed: rng.random(),
+        });
+        let mut adversary = self.adversary_strategy().build();
+        let mut record = GameRecord::default();
+
+        while record.turns < self.max_turns {
+            record.turns += 1;
+
+            // Fill the hand up from the deck, cards kept from the last turn are offered again
+            game.step(GameAction::DrawCards {
+                shuffle_seed: rng.random(),
+            });
+            let hand = game.state.deck.hand.clone();
+
+            if hand.is_empty() {
+                record.stalled = true;
+                break;
+            }
+
+            record.cards_offered.extend(hand.iter().map(|card| card.id));
+
+            // A hand of blocked cards is redrawn once, if that doesn't help the turn goes by
+            if !game.has_playable_card() {
+                game.step(GameAction::Mulligan {
+                    card_ids: hand.iter().map(|card| card.id).collect(),
+                    shuffle_seed: rng.random(),
+                });
+            }
+            let playable: Vec<ActivityCard> = game
+                .state
+                .deck
+                .hand
+                .iter()
+                .filter(|card| game.can_play_card(card).0)
+                .cloned()
+                .collect();
+
+            if playable.is_empty() {
+                record.passed_turns += 1;
+                game.step(GameAction::PassTurn);
+            } else {
+                let card = playable[policy.choose_card(&game, &playable, rng)].clone();
+                let before = resource_total(&game.state);
+                game.step(GameAction::PlayCard {
+                    card: card.clone(),
+                    cutscene_roll: rng.random::<f32>(),
+                });
+                game.step(GameAction::CompleteAction { card: card.clone() });
+                game.step(GameAction::EndCutscene);
+                record
+                    .cards_played
+                    .push((card.id, resource_total(&game.state) - before));
+            }
+
+            // The adversary picks from its own hand
+            let mut adversary_deck =
+                game.deal_adversary_cards(&self.content.schizophrenic_cards, rng);
+            adversary_deck.truncate(ADVERSARY_HAND_SIZE);
+            game.step(GameAction::DrawAdversaryCards {
+                count: adversary_deck.len(),
+            });
+            if !adversary_deck.is_empty() {
+                let index = adversary.choose_card(&game, &adversary_deck, rng);
+                let card = adversary_deck.swap_remove(index);
+                game.step(GameAction::PlayAdversaryCard { card });
+            }
+            game.step(GameAction::EndCutscene);
+            game.step(GameAction::EndTurn);
+
+            if game.phase.current_phase == GamePhase::DeckBuilding {
+                self.build_deck(&mut game, rng);
+            }
+
+            // Once per turn here, the running game resolves after every change
+            if let Some(game_over) = resolve_end_game(&self.content.endgame_scenarios, &game) {
+                record.result = game_over.result;
+                record.scenario = game_over.scenario.map(|scenario| scenario.id);
+                break;
+            }
+        }
+
+        record
+    }
+
+    /// Spend every credit on the cards offered, in the order they are offered
+    fn build_deck(&self, game: &mut Game, rng: &mut StdRng) {
+        game.step(GameAction::StockShop {
+            cards: self.content.activity_cards.clone(),
+            shuffle_seed: rng.random(),
+        });
+        let offer = game.state.shop_offer.clone().unwrap_or_default();
+        for card in offer {
+            if game.state.credits < CARD_PRICE {
+                break;
+            }
+            game.step(GameAction::BuyCard { card_id: card.id });
+        }
+        game.step(GameAction::FinishDeckBuilding);
+    }
+
+    fn adversary_strategy(&self) -> AdversaryStrategyKind {
+        self.adversary.unwrap_or(self.difficulty.adversary_strategy)
+    }
+
+    /// Play `games` games, each seeded from `seed` and its index
+    pub fn run(&self, policy: &mut dyn Policy, games: usize, seed: u64) -> SimulationReport {
+        let mut report = SimulationReport {
+            policy: policy.name(),
+            adversary: self.adversary_strategy().build().name(),
+            difficulty: self.difficulty.id.clone(),
+            ..Default::default()
+        };
+
+        for card in &self.content.activity_cards {
+            report.cards.insert(
+                card.id,
+                CardStats {
+                    name: card.name.clone(),
+                    ..Default::default()
+                },
+            );
+        }
+
+        for index in 0..games {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(index as u64));
+            let record = self.play(policy, &mut rng);
+            report.add(&record);
+        }
+
+        report
+    }
+}
+
+fn resource_total(state: &GameState) -> f32 {
+    state.sleep + state.health + state.mental_health + state.food
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CardStats {
+    pub name: String,
+    pub offered: u32,
+    pub played: u32,
+    pub total_impact: f32,
+    pub games_played_in: u32,
+    pub wins_when_played: u32,
+}
+
+impl CardStats {
+    /// Share of the times the card was in hand that it was also played
+    pub fn play_rate(&self) -> f32 {
+        ratio(self.played, self.offered)
+    }
+
+    /// Average change of all resources combined per play
+    pub fn average_impact(&self) -> f32 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.total_impact / self.played as f32
+        }
+    }
+
+    pub fn win_rate_when_played(&self) -> f32 {
+        ratio(self.wins_when_played, self.games_played_in)
+    }
+}
+
+/// Aggregated results of many simulated games with one policy
+#[derive(Debug, Clone, Default)]
+pub struct SimulationReport {
+    pub policy: String,
+    pub adversary: String,
+    pub difficulty: String,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub stalled: u32,
+    pub total_turns: u64,
+    pub passed_turns: u64,
+    pub results: BTreeMap<String, u32>,
+    pub scenarios: BTreeMap<String, u32>,
+    pub cards: BTreeMap<u32, CardStats>,
+}
+
+impl SimulationReport {
+    pub fn add(&mut self, record: &GameRecord) {
+        self.games += 1;
+        self.total_turns += record.turns as u64;
+        self.passed_turns += record.passed_turns as u64;
+
+        match &record.result {
+            Some(EndGameResult::Success(_)) => self.wins += 1,
+            Some(EndGameResult::Failure(_)) => self.losses += 1,
+            None => {}
+        }
+        if record.stalled {
+            self.stalled += 1;
+        }
+
+        let result = match &record.result {
+            Some(result) => format!("{result:?}"),
+            None if record.stalled => "Stalled".to_string(),
+            None => "NoResult".to_string(),
+        };
+        *self.results.entry(result).or_default() += 1;
+
+        let scenario = record
+            .scenario
+            .clone()
+            .unwrap_or_else(|| "none".to_string());
+        *self.scenarios.entry(scenario).or_default() += 1;
+
+        for id in &record.cards_offered {
+            self.cards.entry(*id).or_default().offered += 1;
+        }
+        for (id, impact) in &record.cards_played {
+            let stats = self.cards.entry(*id).or_default();
+            stats.played += 1;
+            stats.total_impact += impact;
+        }
+
+        let played_ids: HashSet<u32> = record.cards_played.iter().map(|(id, _)| *id).collect();
+        for id in played_ids {
+            let stats = self.cards.entry(id).or_default();
+            stats.games_played_in += 1;
+            if record.is_win() {
+                stats.wins_when_played += 1;
+            }
+        }
+    }
+
+    pub fn average_length(&self) -> f32 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_turns as f32 / self.games as f32
+        }
+    }
+}
+
+fn ratio(part: u32, total: u32) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f32 / total as f32
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "=== Policy: {} vs {} on {} ({} games) ===",
+            self.policy, self.adversary, self.difficulty, self.games
+        )?;
+        writeln!(
+            f,
+            "Wins {:.1}%  Losses {:.1}%  Stalled {:.1}%  Average length {:.1} turns",
+            ratio(self.wins, self.games) * 100.0,
+            ratio(self.losses, self.games) * 100.0,
+            ratio(self.stalled, self.games) * 100.0,
+            self.average_length()
+        )?;
+        writeln!(
+            f,
+            "Passed turns {:.1}%",
+            self.passed_turns as f32 / self.total_turns.max(1) as f32 * 100.0
+        )?;
+
+        writeln!(f, "\nBy EndGameResult:")?;
+        for (result, count) in &self.results {
+            writeln!(
+                f,
+                "  {:<32} {:>6} {:>6.1}%",
+                result,
+                count,
+                ratio(*count, self.games) * 100.0
+            )?;
+        }
+
+        writeln!(f, "\nBy EndGameScenario:")?;
+        for (scenario, count) in &self.scenarios {
+            writeln!(
+                f,
+                "  {:<32} {:>6} {:>6.1}%",
+                scenario,
+                count,
+                ratio(*count, self.games) * 100.0
+            )?;
+        }
+
+        writeln!(f, "\nCards:")?;
+        writeln!(
+            f,
+            "  {:>3} {:<28} {:>8} {:>8} {:>7} {:>8} {:>8}",
+            "id", "name", "offered", "played", "rate", "impact", "win%"
+        )?;
+        for (id, stats) in &self.cards {
+            writeln!(
+                f,
+                "  {:>3} {:<28} {:>8} {:>8} {:>6.1}% {:>8.1} {:>7.1}%",
+                id,
+                stats.name,
+                stats.offered,
+                stats.played,
+                stats.play_rate() * 100.0,
+                stats.average_impact(),
+                stats.win_rate_when_played() * 100.0
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1362 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cards::*;
+use crate::condition::Condition;
+use crate::deck::Deck;
+use crate::difficulty::Difficulty;
+use crate::engine::{GameEvent, GameStep};
+use crate::history::PlayHistory;
+use crate::psych::{PsychStat, PsychStats};
+use crate::status_effects::{StackingMode, StatusEffectDefinitions};
+
+/// How far a played adversary card pushes the stats it was dealt on past their thresholds
+const ADVERSARY_PSYCH_PUSH: f32 = 5.0;
+
+// Enhanced GamePhase enum with new adversary phases
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GamePhase {
+    CardDraw,
+    CardSelection,
+    CharacterAction,
+    AdversaryCardDraw,
+    AdversaryCardSelection,
+    TurnOver,
+    /// Between days, the player spends credits on adding cards to or removing them from the deck
+    DeckBuilding,
+}
+
+/// What an adversary card does to the player when it is played
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct AdversaryEffects {
+    pub player_sleep_change: f32,
+    pub player_health_change: f32,
+    pub player_mental_change: f32,
+    pub player_food_change: f32,
+    pub status_effects: Vec<StatusEffectApplication>,
+    pub environmental_changes: Vec<EnvironmentalChange>,
+}
+
+/// When an adversary card may be dealt, every set condition has to hold
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct AdversaryConditions {
+    pub min_crisis_level: Option<CrisisLevel>,
+    pub max_crisis_level: Option<CrisisLevel>,
+    pub required_time_of_day: Option<Vec<TimeOfDay>>,
+    pub player_resource_thresholds: Option<ResourceThresholds>,
+    pub turn_number_range: Option<(u32, u32)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ResourceThresholds {
+    pub min_sleep: Option<f32>,
+    pub max_sleep: Option<f32>,
+    pub min_health: Option<f32>,
+    pub max_health: Option<f32>,
+    pub min_mental: Option<f32>,
+    pub max_mental: Option<f32>,
+    pub min_food: Option<f32>,
+    pub max_food: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum AdversaryCardType {
+    Crisis,        // Negative events that challenge the player
+    Environmental, // Changes to the game environment
+    Social,        // Social pressures or interactions
+    #[default]
+    Random, // Random events
+    Consequence,   // Direct consequences of player actions
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+pub enum EnvironmentalChange {
+    RemoveObject {
+        object: String,
+    },
+    AddObject {
+        object: String,
+    },
+    LockResource {
+        resource: ResourceType,
+        turns: u32,
+        #[serde(default)]
+        kind: LockKind,
+    },
+}
+
+impl AdversaryConditions {
+    pub fn is_met(&self, game_state: &GameState, turn_number: u32) -> bool {
+        if let Some(min_crisis_level) = self.min_crisis_level
+            && game_state.crisis_level < min_crisis_level
+        {
+            return false;
+        }
+        if let Some(max_crisis_level) = self.max_crisis_level
+            && game_state.crisis_level > max_crisis_level
+        {
+            return false;
+        }
+        if let Some(times) = &self.required_time_of_day
+            && !times.contains(&game_state.time_of_day)
+        {
+            return false;
+        }
+        if let Some(thresholds) = &self.player_resource_thresholds
+            && !thresholds.is_met(game_state)
+        {
+            return false;
+        }
+        if let Some((first_turn, last_turn)) = self.turn_number_range
+            && (turn_number < first_turn || turn_number > last_turn)
+        {
+            return false;
+        }
+        if let Some(condition) = &self.when
+            && !condition.evaluate(game_state, turn_number)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl ResourceThresholds {
+    pub fn is_met(&self, game_state: &GameState) -> bool {
+        let within = |value: f32, min: Option<f32>, max: Option<f32>| {
+            min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+        };
+
+        within(game_state.sleep, self.min_sleep, self.max_sleep)
+            && within(game_state.health, self.min_health, self.max_health)
+            && within(game_state.mental_health, self.min_mental, self.max_mental)
+            && within(game_state.food, self.min_food, self.max_food)
+    }
+}
+
+// Game Data Structures (existing ones remain the same)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveStatusEffect {
+    pub effect: StatusEffect,
+    pub remaining_duration: u32,
+    pub intensity: f32,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerAction {
+    pub turn: u32,
+    /// Missing in saves from before the history kept the card
+    #[serde(default)]
+    pub card_id: Option<u32>,
+    pub resources_before: (f32, f32, f32, f32),
+    pub resources_after: (f32, f32, f32, f32),
+    pub timestamp: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum EndGameResult {
+    Success(SuccessReason),
+    Failure(FailureReason),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SuccessReason {
+    Stability,
+    Recovery,
+    Resilience,
+    Growth,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum FailureReason {
+    CompleteBreakdown,
+    HealthCrisis,
+    MentalCollapse,
+    TimeLimit,
+    CascadeFailure,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CutsceneTrigger {
+    CardEffect,
+    MoodChange,
+    TimeOfDay,
+    ResourceThreshold,
+    TurnEnd,
+    AdversaryAction, // New trigger type
+}
+
+// Enhanced GamePhaseState with adversary state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamePhaseState {
+    pub current_phase: GamePhase,
+    pub previous_phase: Option<GamePhase>,
+    pub turn_number: u32,
+    pub cards_drawn_count: usize,
+    pub selected_card_id: Option<u32>,
+    pub pending_cutscene: Option<String>,
+    pub cutscene_active: bool,
+    pub pending_post_action_cutscene: Option<PendingCutscene>,
+    // New adversary fields
+    pub adversary_cards_drawn: Vec<SchizophrenicCard>,
+    pub selected_adversary_card: Option<SchizophrenicCard>,
+    pub adversary_deck: Vec<SchizophrenicCard>, // Available adversary cards
+    /// Set once the rules end the game, no action is taken after that
+    #[serde(default)]
+    pub game_over: Option<EndGameResult>,
+    /// A day went by this turn, the deck is built before the next draw
+    #[serde(default)]
+    pub deck_building_due: bool,
+    /// The hand was already redrawn this turn
+    #[serde(default)]
+    pub mulligan_used: bool,
+}
+
+impl Default for GamePhaseState {
+    fn default() -> Self {
+        Self {
+            current_phase: GamePhase::CardDraw,
+            previous_phase: None,
+            turn_number: 1,
+            cards_drawn_count: 0,
+            selected_card_id: None,
+            pending_cutscene: None,
+            cutscene_active: false,
+            pending_post_action_cutscene: None,
+            adversary_cards_drawn: Vec::new(),
+            selected_adversary_card: None,
+            adversary_deck: Vec::new(), // Will be loaded from JSON
+            game_over: None,
+            deck_building_due: false,
+            mulligan_used: false,
+        }
+    }
+}
+
+// Enhanced GameState remains mostly the same but with new fields
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameState {
+    // Time
+    pub current_hour: f32,
+    pub current_day: u32,
+    pub time_speed: f32,
+
+    // Resources
+    pub sleep: f32,
+    pub health: f32,
+    pub mental_health: f32,
+    pub food: f32,
+
+    // Derived states
+    pub current_mood: Mood,
+    pub time_of_day: TimeOfDay,
+
+    // Enhanced fields
+    pub status_effects: Vec<ActiveStatusEffect>,
+    pub locked_resources: HashMap<ResourceType, ResourceLock>,
+    pub card_cooldowns: HashMap<u32, u32>,
+    pub used_one_time_cards: Vec<u32>,
+    pub daily_used_cards: Vec<u32>,
+    pub crisis_level: CrisisLevel,
+    pub consecutive_stable_days: u32,
+    pub action_history: Vec<PlayerAction>,
+    pub available_objects: Vec<String>,
+    pub negative_card_count: u32,
+    pub shown_cutscenes: HashSet<u32>,
+
+    pub active_trigger_symptoms: Vec<String>,
+    #[serde(default)]
+    pub adversary_actions: Vec<AdversaryAction>,
+    #[serde(default)]
+    pub psych_stats: PsychStats,
+    #[serde(default)]
+    pub history: PlayHistory,
+    #[serde(default)]
+    pub deck: Deck,
+    /// Earned on stable days and spent between days on the deck
+    #[serde(default)]
+    pub credits: u32,
+    /// Cards on sale while the shop is open between days, kept so a saved game reopens the same shop
+    #[serde(default)]
+    pub shop_offer: Option<Vec<ActivityCard>>,
+    /// Content rather than state, provided by whoever sets up the game
+    #[serde(skip)]
+    pub status_effect_definitions: StatusEffectDefinitions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdversaryAction {
+    pub turn: u32,
+    pub card_played: SchizophrenicCard,
+    pub effects_applied: AdversaryEffects,
+    pub timestamp: f32,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self {
+            current_hour: 10.0,
+            current_day: 1,
+            time_speed: 1.0,
+            sleep: 70.0,
+            health: 80.0,
+            mental_health: 60.0,
+            food: 50.0,
+            current_mood: Mood::Neutral,
+            time_of_day: TimeOfDay::Morning,
+            status_effects: Vec::new(),
+            locked_resources: HashMap::new(),
+            card_cooldowns: HashMap::new(),
+            used_one_time_cards: Vec::new(),
+            daily_used_cards: Vec::new(),
+            crisis_level: CrisisLevel::None,
+            consecutive_stable_days: 0,
+            action_history: Vec::new(),
+            available_objects: vec![
+                "bed".to_string(),
+                "kitchen".to_string(),
+                "bathroom".to_string(),
+                "tv".to_string(),
+                "computer".to_string(),
+                "phone".to_string(),
+                "fridge".to_string(),
+                "gameconsole".to_string(),
+                "window".to_string(),
+            ],
+            negative_card_count: 0,
+            shown_cutscenes: HashSet::new(),
+            active_trigger_symptoms: Vec::new(),
+            adversary_actions: Vec::new(),
+            psych_stats: PsychStats::default(),
+            history: PlayHistory::default(),
+            deck: Deck::default(),
+            credits: 0,
+            shop_offer: None,
+            status_effect_definitions: StatusEffectDefinitions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingCutscene {
+    pub cutscene_id: String,
+    pub card_id: Option<u32>,
+    pub trigger_reason: CutsceneTrigger,
+}
+
+// Implementation methods for adversary logic
+impl GameState {
+    // Existing methods remain the same...
+    pub fn can_play_card(&self, card: &ActivityCard, turn_number: u32) -> (bool, Vec<String>) {
+        let mut blocking_conditions = Vec::new();
+
+        // Check resource requirements
+        if let Some(min_sleep) = card.conditions.min_sleep
+            && self.sleep < min_sleep
+        {
+            blocking_conditions.push(format!(
+                "Need at least {} Sleep (have {})",
+                min_sleep, self.sleep
+            ));
+        }
+        if let Some(max_sleep) = card.conditions.max_sleep
+            && self.sleep > max_sleep
+        {
+            blocking_conditions.push(format!("Sleep too high (max {max_sleep})"));
+        }
+        if let Some(min_health) = card.conditions.min_health
+            && self.health < min_health
+        {
+            blocking_conditions.push(format!("Need at least {min_health} Health"));
+        }
+        if let Some(max_health) = card.conditions.max_health
+            && self.health > max_health
+        {
+            blocking_conditions.push(format!("Health too high (max {max_health})"));
+        }
+        if let Some(min_mental) = card.conditions.min_mental
+            && self.mental_health < min_mental
+        {
+            blocking_conditions.push(format!("Need at least {min_mental} Mental Health"));
+        }
+        if let Some(max_mental) = card.conditions.max_mental
+            && self.mental_health > max_mental
+        {
+            blocking_conditions.push(format!("Mental Health too high (max {max_mental})"));
+        }
+        if let Some(min_food) = card.conditions.min_food
+            && self.food < min_food
+        {
+            blocking_conditions.push(format!("Need at least {min_food} Food"));
+        }
+        if let Some(max_food) = card.conditions.max_food
+            && self.food > max_food
+        {
+            blocking_conditions.push(format!("Food too high (max {max_food})"));
+        }
+
+        // Check mood requirements
+        if let Some(required_mood) = &card.conditions.required_mood
+            && self.current_mood != *required_mood
+        {
+            blocking_conditions.push(format!("Must be {required_mood:?} mood"));
+        }
+        if let Some(forbidden_mood) = &card.conditions.forbidden_mood
+            && self.current_mood == *forbidden_mood
+        {
+            blocking_conditions.push(format!("Cannot be {forbidden_mood:?} mood"));
+        }
+
+        // Check time requirements
+        if let Some(allowed_times) = &card.conditions.time_of_day
+            && !allowed_times.contains(&self.time_of_day)
+        {
+            blocking_conditions.push(format!("Wrong time of day (need {allowed_times:?})"));
+        }
+
+        // Check day range
+        if let Some((min_day, max_day)) = card.conditions.day_range
+            && (self.current_day < min_day || self.current_day > max_day)
+        {
+            blocking_conditions.push(format!("Wrong day (need days {min_day}-{max_day})"));
+        }
+
+        // Check required objects
+        if let Some(required_objects) = card.conditions.required_objects.clone() {
+            for required_object in &required_objects {
+                if !self.available_objects.contains(required_object) {
+                    blocking_conditions.push(format!("Need {required_object}"));
+                }
+            }
+        }
+
+        // Check crisis level
+        if let Some(required_crisis) = &card.conditions.crisis_level
+            && self.crisis_level != *required_crisis
+        {
+            blocking_conditions.push(format!("Need {required_crisis:?} crisis level"));
+        }
+
+        if let Some(condition) = &card.conditions.when
+            && !condition.evaluate(self, turn_number)
+        {
+            blocking_conditions.push(format!("Needs {condition}"));
+        }
+
+        // Check cooldowns
+        if let Some(cooldown) = card.cooldown
+            && let Some(&last_used_turn) = self.card_cooldowns.get(&card.id)
+        {
+            let turns_since = turn_number.saturating_sub(last_used_turn);
+            if turns_since < cooldown {
+                blocking_conditions.push(format!(
+                    "On cooldown for {} more turns",
+                    cooldown - turns_since
+                ));
+            }
+        }
+
+        // Check one-time use
+        if card.one_time_use && self.used_one_time_cards.contains(&card.id) {
+            blocking_conditions.push("Already used (one-time only)".to_string());
+        }
+
+        // Check daily use
+        if matches!(card.availability, CardAvailability::DailyReset)
+            && self.daily_used_cards.contains(&card.id)
+        {
+            blocking_conditions.push("Already used today".to_string());
+        }
+
+        // Check costs
+        if !self.can_afford_costs(&card.costs) {
+            if self.sleep < card.costs.sleep_cost {
+                blocking_conditions.push(format!(
+                    "Not enough Sleep ({} needed)",
+                    card.costs.sleep_cost
+                ));
+            }
+            if self.health < card.costs.health_cost {
+                blocking_conditions.push(format!(
+                    "Not enough Health ({} needed)",
+                    card.costs.health_cost
+                ));
+            }
+            if self.mental_health < card.costs.mental_cost {
+                blocking_conditions.push(format!(
+                    "Not enough Mental ({} needed)",
+                    card.costs.mental_cost
+                ));
+            }
+            if self.food < card.costs.food_cost {
+                blocking_conditions
+                    .push(format!("Not enough Food ({} needed)", card.costs.food_cost));
+            }
+        }
+
+        // Check locks on the resources the card spends
+        for resource_type in ResourceType::ALL {
+            if card.costs.spends(resource_type)
+                && let Some(lock) = self.spending_lock(resource_type)
+            {
+                blocking_conditions.push(format!(
+                    "{resource_type:?} is locked for {} more turns",
+                    lock.turns
+                ));
+            }
+        }
+
+        (blocking_conditions.is_empty(), blocking_conditions)
+    }
+
+    /// Apply what an adversary card does, `aggression` scales its resource changes
+    pub fn apply_adversary_card_effects(
+        &mut self,
+        card: &SchizophrenicCard,
+        aggression: f32,
+    ) -> Vec<GameEvent> {
+        let mut status_events = Vec::new();
+
+        // Clear previous trigger symptoms and add new ones
+        self.active_trigger_symptoms.clear();
+        self.active_trigger_symptoms
+            .extend(card.conditions.trigger_symptoms.clone());
+
+        // A symptom that breaks through feeds itself
+        for (stat, threshold) in card.conditions.thresholds() {
+            self.psych_stats
+                .change(stat, threshold.deepen(ADVERSARY_PSYCH_PUSH));
+        }
+
+        let effects = &card.effects;
+        let resource_changes = [
+            (ResourceType::Sleep, effects.player_sleep_change),
+            (ResourceType::Health, effects.player_health_change),
+            (ResourceType::Mental, effects.player_mental_change),
+            (ResourceType::Food, effects.player_food_change),
+        ];
+        for (resource_type, change) in resource_changes {
+            self.change_resource(resource_type, change * aggression);
+        }
+
+        for status_app in &effects.status_effects {
+            if self.add_status_effect(status_app, &card.card_name) {
+                status_events.push(GameEvent::StatusEffectApplied {
+                    effect: status_app.effect,
+                    duration: status_app.duration,
+                    source: card.card_name.clone(),
+                });
+            }
+        }
+
+        for change in &effects.environmental_changes {
+            match change {
+                EnvironmentalChange::RemoveObject { object } => {
+                    self.available_objects
+                        .retain(|available| available != object);
+                }
+                EnvironmentalChange::AddObject { object } => {
+                    if !self.available_objects.contains(object) {
+                        self.available_objects.push(object.clone());
+                    }
+                }
+                EnvironmentalChange::LockResource {
+                    resource,
+                    turns,
+                    kind,
+                } => {
+                    self.locked_resources.insert(
+                        *resource,
+                        ResourceLock {
+                            kind: *kind,
+                            turns: *turns,
+                        },
+                    );
+                }
+            }
+        }
+
+        status_events
+    }
+
+    /// Clear trigger symptoms (called at turn end)
+    pub fn clear_trigger_symptoms(&mut self) {
+        self.active_trigger_symptoms.clear();
+    }
+
+    /// Check if a specific trigger symptom is currently active
+    pub fn has_trigger_symptom(&self, symptom: &str) -> bool {
+        self.active_trigger_symptoms.contains(&symptom.to_string())
+    }
+
+    /// Get all active trigger symptoms
+    pub fn get_active_trigger_symptoms(&self) -> &Vec<String> {
+        &self.active_trigger_symptoms
+    }
+
+    pub fn can_afford_costs(&self, costs: &CardCosts) -> bool {
+        self.sleep >= costs.sleep_cost
+            && self.health >= costs.health_cost
+            && self.mental_health >= costs.mental_cost
+            && self.food >= costs.food_cost
+    }
+
+    pub fn calculate_crisis_level(&self) -> CrisisLevel {
+        let resources_below_30 = [self.sleep, self.health, self.mental_health, self.food]
+            .iter()
+            .filter(|&&r| r < 30.0)
+            .count();
+
+        let resources_below_15 = [self.sleep, self.health, self.mental_health, self.food]
+            .iter()
+            .filter(|&&r| r < 15.0)
+            .count();
+
+        let resources_below_10 = [self.sleep, self.health, self.mental_health, self.food]
+            .iter()
+            .filter(|&&r| r < 10.0)
+            .count();
+
+        match (resources_below_10, resources_below_15, resources_below_30) {
+            (2.., _, _) => CrisisLevel::Critical,
+            (1, _, _) => CrisisLevel::Severe,
+            (0, 1, _) => CrisisLevel::Moderate,
+            (0, 0, 1..) => CrisisLevel::Mild,
+            _ => CrisisLevel::None,
+        }
+    }
+
+    pub fn apply_card_effects(&mut self, card: &ActivityCard, turn_number: u32) -> Vec<GameEvent> {
+        let mut status_events = Vec::new();
+
+        // Store resources before for history
+        let resources_before = (self.sleep, self.health, self.mental_health, self.food);
+
+        // Pay costs
+        for resource_type in ResourceType::ALL {
+            self.change_resource(resource_type, -card.costs.resource_cost(resource_type));
+        }
+
+        // Apply effects with multipliers
+        let effect_multiplier = self.calculate_effect_multiplier();
+
+        for resource_type in ResourceType::ALL {
+            self.change_resource(
+                resource_type,
+                card.effects.resource_effect(resource_type) * effect_multiplier,
+            );
+        }
+
+        self.psych_stats.apply(&card.psych_effects);
+
+        // Apply status effects
+        for status_app in &card.status_effects {
+            if self.add_status_effect(status_app, &card.name) {
+                status_events.push(GameEvent::StatusEffectApplied {
+                    effect: status_app.effect,
+                    duration: status_app.duration,
+                    source: card.name.clone(),
+                });
+            }
+        }
+
+        // Handle additional costs
+        for additional_cost in &card.costs.additional_costs {
+            match additional_cost {
+                AdditionalCost::AddNegativeCard { .. } => {
+                    self.negative_card_count += 1;
+                }
+                AdditionalCost::LockResource {
+                    resource,
+                    turns,
+                    kind,
+                } => {
+                    self.locked_resources.insert(
+                        *resource,
+                        ResourceLock {
+                            kind: *kind,
+                            turns: *turns,
+                        },
+                    );
+                }
+                AdditionalCost::ConsumeAllOfResource { resource } => {
+                    self.change_resource(*resource, -100.0);
+                }
+            }
+        }
+
+        // Update cooldown
+        if card.cooldown.is_some() {
+            self.card_cooldowns.insert(card.id, turn_number);
+        }
+
+        // Mark usage
+        if card.one_time_use {
+            self.used_one_time_cards.push(card.id);
+        }
+
+        if matches!(card.availability, CardAvailability::DailyReset) {
+            self.daily_used_cards.push(card.id);
+        }
+
+        // Update crisis level
+        self.crisis_level = self.calculate_crisis_level();
+        self.history
+            .record_crisis_level(self.crisis_level, self.current_day);
+
+        // Add to action history
+        self.history.record_card(&card.card_type);
+        let resources_after = (self.sleep, self.health, self.mental_health, self.food);
+        self.action_history.push(PlayerAction {
+            turn: turn_number,
+            card_id: Some(card.id),
+            resources_before,
+            resources_after,
+            timestamp: self.current_hour,
+        });
+
+        status_events
+    }
+
+    fn calculate_effect_multiplier(&self) -> f32 {
+        let mut multiplier = 1.0;
+
+        for status in &self.status_effects {
+            if let Some(definition) = self.status_effect_definitions.get(status.effect) {
+                multiplier += definition.card_effect_multiplier * status.intensity;
+            }
+        }
+
+        multiplier.max(0.1)
+    }
+
+    /// Apply an effect, or combine it with the running one as its definition says, returns
+    /// whether the active effects changed
+    pub fn add_status_effect(
+        &mut self,
+        application: &StatusEffectApplication,
+        source: &str,
+    ) -> bool {
+        let (stacking, max_intensity) = self
+            .status_effect_definitions
+            .get(application.effect)
+            .map_or((StackingMode::Refresh, 1.0), |definition| {
+                (definition.stacking, definition.max_intensity)
+            });
+        let mut intensity = application.intensity.min(max_intensity);
+
+        match stacking {
+            StackingMode::Refresh => {
+                self.status_effects
+                    .retain(|status| status.effect != application.effect);
+            }
+            StackingMode::Stack => {
+                // Stacks run out one by one, together they stay within the max intensity
+                let stacked: f32 = self
+                    .status_effects
+                    .iter()
+                    .filter(|status| status.effect == application.effect)
+                    .map(|status| status.intensity)
+                    .sum();
+                intensity = intensity.min(max_intensity - stacked);
+                if intensity <= 0.0 {
+                    return false;
+                }
+            }
+            StackingMode::Max => {
+                if let Some(active) = self
+                    .status_effects
+                    .iter_mut()
+                    .find(|status| status.effect == application.effect)
+                {
+                    if active.remaining_duration >= application.duration
+                        && active.intensity >= intensity
+                    {
+                        return false;
+                    }
+                    active.remaining_duration = active.remaining_duration.max(application.duration);
+                    active.intensity = active.intensity.max(intensity);
+                    active.source = source.to_string();
+                    return true;
+                }
+            }
+        }
+
+        self.status_effects.push(ActiveStatusEffect {
+            effect: application.effect,
+            remaining_duration: application.duration,
+            intensity,
+            source: source.to_string(),
+        });
+        true
+    }
+
+    fn has_status_effect(&self, matches: impl Fn(&StatusEffect) -> bool) -> bool {
+        self.status_effects
+            .iter()
+            .any(|status| matches(&status.effect))
+    }
+
+    /// Drift of the hidden stats over a turn, driven by the visible resources and status effects
+    pub fn drift_psych_stats(&mut self) {
+        let sleep_deprived = self.sleep < 30.0;
+        let stable = self.has_status_effect(|effect| {
+            matches!(effect, StatusEffect::Stable | StatusEffect::Motivated)
+        });
+        let anxious = self.has_status_effect(|effect| {
+            matches!(
+                effect,
+                StatusEffect::Anxious | StatusEffect::Overwhelmed | StatusEffect::Insomnia
+            )
+        });
+        let stats = &self.psych_stats;
+        let isolated = stats.social_connection < 30.0;
+
+        let mut reality_change = -1.0;
+        if sleep_deprived {
+            reality_change -= 2.0;
+        }
+        if isolated {
+            reality_change -= 1.0;
+        }
+        if stats.anxiety_level > 70.0 {
+            reality_change -= 1.0;
+        }
+        if stable {
+            reality_change += 3.0;
+        }
+
+        let changes = [
+            (PsychStat::RealityPerception, reality_change),
+            // Follows the visible mental health with a delay
+            (
+                PsychStat::MentalState,
+                (self.mental_health - stats.mental_state) * 0.2,
+            ),
+            (
+                PsychStat::SensorySensitivity,
+                if self.sleep < 40.0 { 3.0 } else { -2.0 },
+            ),
+            // Staying inside slowly cuts the character off
+            (PsychStat::SocialConnection, -2.0),
+            (
+                PsychStat::IdentityStability,
+                match (stable, stats.reality_perception < 40.0) {
+                    (true, _) => 1.0,
+                    (false, true) => -2.0,
+                    (false, false) => -0.5,
+                },
+            ),
+            (
+                PsychStat::AnxietyLevel,
+                match (anxious, isolated) {
+                    (true, _) => 3.0,
+                    (false, true) => 1.0,
+                    (false, false) => -2.0,
+                },
+            ),
+        ];
+        for (stat, change) in changes {
+            self.psych_stats.change(stat, change);
+        }
+    }
+
+    /// Resource drain of all active effects for one turn, scaled by their intensity
+    pub fn status_effect_step(&self) -> GameStep {
+        let mut step = GameStep::default();
+        for status in &self.status_effects {
+            let Some(definition) = self.status_effect_definitions.get(status.effect) else {
+                continue;
+            };
+            let drain = &definition.turn_drain;
+            step.sleep_change += drain.sleep * status.intensity;
+            step.health_change += drain.health * status.intensity;
+            step.mental_health_change += drain.mental * status.intensity;
+            step.food_change += drain.food * status.intensity;
+        }
+        step
+    }
+
+    /// Whether an active effect keeps the resource from going up
+    pub fn is_recovery_blocked(&self, resource_type: ResourceType) -> bool {
+        self.status_effects.iter().any(|status| {
+            self.status_effect_definitions
+                .get(status.effect)
+                .is_some_and(|definition| definition.blocks_recovery.contains(&resource_type))
+        })
+    }
+
+    pub fn tick_status_effects(&mut self) -> Vec<GameEvent> {
+        let mut expired_events = Vec::new();
+
+        let mut i = 0;
+        while i < self.status_effects.len() {
+            self.status_effects[i].remaining_duration -= 1;
+            if self.status_effects[i].remaining_duration == 0 {
+                let expired = self.status_effects.remove(i);
+                expired_events.push(GameEvent::StatusEffectExpired {
+                    effect: expired.effect,
+                });
+            } else {
+                i += 1;
+            }
+        }
+
+        for lock in self.locked_resources.values_mut() {
+            lock.turns = lock.turns.saturating_sub(1);
+        }
+        self.locked_resources.retain(|_, lock| lock.turns > 0);
+
+        expired_events
+    }
+
+    pub fn check_end_game_conditions(&self, difficulty: &Difficulty) -> Option<EndGameResult> {
+        // Failure conditions
+        let all_resources_critical = [self.sleep, self.health, self.mental_health, self.food]
+            .iter()
+            .all(|&r| r < 5.0);
+
+        if all_resources_critical {
+            return Some(EndGameResult::Failure(FailureReason::CompleteBreakdown));
+        }
+
+        if self.health <= 0.0 {
+            return Some(EndGameResult::Failure(FailureReason::HealthCrisis));
+        }
+
+        if self.mental_health <= 0.0 && self.has_depression_spiral() {
+            return Some(EndGameResult::Failure(FailureReason::MentalCollapse));
+        }
+
+        if self.current_day >= difficulty.day_limit && self.crisis_level == CrisisLevel::Critical {
+            return Some(EndGameResult::Failure(FailureReason::TimeLimit));
+        }
+
+        let cascading_count = self
+            .status_effects
+            .iter()
+            .filter(|status| {
+                self.status_effect_definitions
+                    .get(status.effect)
+                    .is_some_and(|definition| definition.cascading)
+            })
+            .count();
+        if cascading_count >= 3 {
+            return Some(EndGameResult::Failure(FailureReason::CascadeFailure));
+        }
+
+        // Success conditions
+        if self.consecutive_stable_days >= difficulty.stable_days_to_win {
+            return Some(EndGameResult::Success(SuccessReason::Stability));
+        }
+
+        let all_resources_high = [self.sleep, self.health, self.mental_health, self.food]
+            .iter()
+            .all(|&r| r >= 80.0);
+
+        if all_resources_high {
+            return Some(EndGameResult::Success(SuccessReason::Recovery));
+        }
+
+        if self.current_day >= difficulty.day_limit && self.crisis_level == CrisisLevel::None {
+            return Some(EndGameResult::Success(SuccessReason::Resilience));
+        }
+
+        None
+    }
+
+    fn has_depression_spiral(&self) -> bool {
+        self.status_effects
+            .iter()
+            .any(|status| status.effect == StatusEffect::Depressed && status.remaining_duration > 5)
+    }
+
+    pub(crate) fn calculate_time_of_day(hour: f32) -> TimeOfDay {
+        match hour {
+            h if (5.0..9.0).contains(&h) => TimeOfDay::EarlyMorning,
+            h if (9.0..12.0).contains(&h) => TimeOfDay::Morning,
+            h if (12.0..17.0).contains(&h) => TimeOfDay::Afternoon,
+            h if (17.0..20.0).contains(&h) => TimeOfDay::Evening,
+            h if !(5.0..20.0).contains(&h) => {
+                if h >= 20.0 {
+                    TimeOfDay::Night
+                } else {
+                    TimeOfDay::LateNight
+                }
+            }
+            _ => TimeOfDay::Morning,
+        }
+    }
+
+    pub(crate) fn calculate_mood(&self) -> Mood {
+        let avg_resources = (self.sleep + self.health + self.mental_health + self.food) / 4.0;
+
+        match () {
+            _ if self.mental_health < 20.0 => Mood::Depressed,
+            _ if self.sleep < 20.0 => Mood::Tired,
+            _ if self.mental_health < 40.0 && self.sleep < 40.0 => Mood::Anxious,
+            _ if avg_resources > 80.0 => Mood::Content,
+            _ if self.mental_health > 90.0 && self.sleep < 30.0 => Mood::Manic,
+            _ => Mood::Neutral,
+        }
+    }
+
+    pub fn get_time_string(&self) -> String {
+        let hour = self.current_hour as u32;
+        let minute = ((self.current_hour % 1.0) * 60.0) as u32;
+        format!("{hour:02}:{minute:02}")
+    }
+
+    pub fn get_day_string(&self) -> String {
+        format!("Day {}", self.current_day)
+    }
+
+    pub fn get_resource_value(&self, resource_type: ResourceType) -> f32 {
+        match resource_type {
+            ResourceType::Sleep => self.sleep,
+            ResourceType::Health => self.health,
+            ResourceType::Mental => self.mental_health,
+            ResourceType::Food => self.food,
+        }
+    }
+
+    pub fn resource_mut(&mut self, resource_type: ResourceType) -> &mut f32 {
+        match resource_type {
+            ResourceType::Sleep => &mut self.sleep,
+            ResourceType::Health => &mut self.health,
+            ResourceType::Mental => &mut self.mental_health,
+            ResourceType::Food => &mut self.food,
+        }
+    }
+
+    /// Part of `delta` that locks and status effects on the resource let through, spending
+    /// locks only hold back the player and are checked before a card is played
+    pub fn allowed_change(&self, resource_type: ResourceType, delta: f32) -> f32 {
+        if self.is_recovery_blocked(resource_type) {
+            return delta.min(0.0);
+        }
+
+        match self.locked_resources.get(&resource_type) {
+            Some(lock) if lock.kind == LockKind::NoGain => delta.min(0.0),
+            _ => delta,
+        }
+    }
+
+    /// The lock keeping the player from spending the resource, if there is one
+    pub fn spending_lock(&self, resource_type: ResourceType) -> Option<&ResourceLock> {
+        self.locked_resources
+            .get(&resource_type)
+            .filter(|lock| lock.kind == LockKind::NoSpend)
+    }
+
+    /// Change a resource, respecting locks, and return the new value
+    pub fn change_resource(&mut self, resource_type: ResourceType, delta: f32) -> f32 {
+        let delta = self.allowed_change(resource_type, delta);
+        let value = self.resource_mut(resource_type);
+        *value = (*value + delta).clamp(0.0, 100.0);
+        *value
+    }
+}
+
+// Helper functions for phase management
+impl GamePhaseState {
+    pub fn get_phase_name(&self) -> &str {
+        match self.current_phase {
+            GamePhase::CardDraw => "Card Draw",
+            GamePhase::CardSelection => "Card Selection",
+            GamePhase::CharacterAction => "Character Action",
+            GamePhase::AdversaryCardDraw => "Adversary Card Draw",
+            GamePhase::AdversaryCardSelection => "Adversary Card Selection",
+            GamePhase::TurnOver => "Turn Over",
+            GamePhase::DeckBuilding => "Deck Building",
+        }
+    }
+
+    pub fn is_cutscene_active(&self) -> bool {
+        self.cutscene_active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Game, GameAction};
+    use crate::status_effects::StatusEffectDefinition;
+
+    fn lock(state: &mut GameState, resource: ResourceType, kind: LockKind) {
+        state
+            .locked_resources
+            .insert(resource, ResourceLock { kind, turns: 3 });
+    }
+
+    fn card_costing(costs: CardCosts) -> ActivityCard {
+        ActivityCard {
+            id: 1,
+            costs,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spending_lock_blocks_cards_that_cost_the_resource() {
+        let mut state = GameState::default();
+        lock(&mut state, ResourceType::Food, LockKind::NoSpend);
+
+        let food_cost = card_costing(CardCosts {
+            food_cost: 5.0,
+            ..Default::default()
+        });
+        let (can_play, blocking_conditions) = state.can_play_card(&food_cost, 1);
+        assert!(!can_play);
+        assert_eq!(blocking_conditions, ["Food is locked for 3 more turns"]);
+
+        let consume_all = card_costing(CardCosts {
+            additional_costs: vec![AdditionalCost::ConsumeAllOfResource {
+                resource: ResourceType::Food,
+            }],
+            ..Default::default()
+        });
+        assert!(!state.can_play_card(&consume_all, 1).0);
+
+        let sleep_cost = card_costing(CardCosts {
+            sleep_cost: 5.0,
+            ..Default::default()
+        });
+        assert!(state.can_play_card(&sleep_cost, 1).0);
+    }
+
+    #[test]
+    fn spending_lock_does_not_stop_drains() {
+        let mut game = Game::new();
+        lock(&mut game.state, ResourceType::Food, LockKind::NoSpend);
+        let food = game.state.food;
+
+        game.step(GameAction::EndTurn);
+        assert_eq!(game.state.food, food + game.difficulty.turn_decay.food);
+
+        game.state.change_resource(ResourceType::Food, -10.0);
+        assert_eq!(
+            game.state.food,
+            food + game.difficulty.turn_decay.food - 10.0
+        );
+    }
+
+    #[test]
+    fn gain_lock_only_holds_back_increases() {
+        let mut state = GameState::default();
+        lock(&mut state, ResourceType::Mental, LockKind::NoGain);
+        let mental = state.mental_health;
+
+        assert_eq!(state.change_resource(ResourceType::Mental, 10.0), mental);
+        assert_eq!(
+            state.change_resource(ResourceType::Mental, -10.0),
+            mental - 10.0
+        );
+    }
+
+    /// State knowing how `effect` stacks, draining `sleep_drain` sleep a turn at intensity 1
+    fn define(
+        state: &mut GameState,
+        effect: StatusEffect,
+        stacking: StackingMode,
+        max_intensity: f32,
+        sleep_drain: f32,
+    ) {
+        state
+            .status_effect_definitions
+            .0
+            .push(StatusEffectDefinition {
+                effect,
+                name: format!("{effect:?}"),
+                description: String::new(),
+                turn_drain: ResourceEffects {
+                    sleep: sleep_drain,
+                    ..Default::default()
+                },
+                blocks_recovery: Vec::new(),
+                card_effect_multiplier: 0.0,
+                stacking,
+                max_intensity,
+                cascading: false,
+                color: String::new(),
+                icon: None,
+            });
+    }
+
+    fn apply(state: &mut GameState, duration: u32, intensity: f32) -> bool {
+        state.add_status_effect(
+            &StatusEffectApplication {
+                effect: StatusEffect::Anxious,
+                duration,
+                intensity,
+            },
+            "test",
+        )
+    }
+
+    fn active(state: &GameState) -> Vec<(u32, f32)> {
+        state
+            .status_effects
+            .iter()
+            .map(|status| (status.remaining_duration, status.intensity))
+            .collect()
+    }
+
+    #[test]
+    fn refresh_replaces_the_running_effect() {
+        let mut state = GameState::default();
+        define(
+            &mut state,
+            StatusEffect::Anxious,
+            StackingMode::Refresh,
+            2.0,
+            0.0,
+        );
+
+        assert!(apply(&mut state, 3, 1.0));
+        assert!(apply(&mut state, 1, 0.5));
+        assert_eq!(active(&state), [(1, 0.5)]);
+
+        assert!(apply(&mut state, 2, 5.0));
+        assert_eq!(active(&state), [(2, 2.0)]);
+    }
+
+    #[test]
+    fn stacks_run_side_by_side_up_to_the_max_intensity() {
+        let mut state = GameState::default();
+        define(
+            &mut state,
+            StatusEffect::Anxious,
+            StackingMode::Stack,
+            2.0,
+            0.0,
+        );
+
+        assert!(apply(&mut state, 3, 1.0));
+        assert!(apply(&mut state, 2, 0.5));
+        assert!(apply(&mut state, 4, 1.0));
+        assert_eq!(active(&state), [(3, 1.0), (2, 0.5), (4, 0.5)]);
+
+        assert!(!apply(&mut state, 5, 1.0));
+        assert_eq!(state.status_effects.len(), 3);
+    }
+
+    #[test]
+    fn max_keeps_the_longer_duration_and_the_stronger_intensity() {
+        let mut state = GameState::default();
+        define(
+            &mut state,
+            StatusEffect::Anxious,
+            StackingMode::Max,
+            2.0,
+            0.0,
+        );
+
+        assert!(apply(&mut state, 3, 0.5));
+        assert!(apply(&mut state, 1, 1.5));
+        assert_eq!(active(&state), [(3, 1.5)]);
+
+        assert!(apply(&mut state, 5, 0.5));
+        assert_eq!(active(&state), [(5, 1.5)]);
+
+        assert!(!apply(&mut state, 2, 1.0));
+        assert_eq!(active(&state), [(5, 1.5)]);
+    }
+
+    #[test]
+    fn undefined_effects_refresh_at_intensity_one() {
+        let mut state = GameState::default();
+
+        assert!(apply(&mut state, 3, 4.0));
+        assert!(apply(&mut state, 2, 0.5));
+        assert_eq!(active(&state), [(2, 0.5)]);
+    }
+
+    #[test]
+    fn status_effects_drain_in_proportion_to_their_intensity() {
+        let mut state = GameState::default();
+        define(
+            &mut state,
+            StatusEffect::Anxious,
+            StackingMode::Stack,
+            2.0,
+            -4.0,
+        );
+        define(
+            &mut state,
+            StatusEffect::Hungry,
+            StackingMode::Refresh,
+            2.0,
+            -2.0,
+        );
+
+        apply(&mut state, 3, 0.5);
+        apply(&mut state, 3, 1.0);
+        state.add_status_effect(
+            &StatusEffectApplication {
+                effect: StatusEffect::Hungry,
+                duration: 3,
+                intensity: 0.5,
+            },
+            "test",
+        );
+        // Effects without a definition drain nothing
+        state.status_effects.push(ActiveStatusEffect {
+            effect: StatusEffect::Sick,
+            remaining_duration: 3,
+            intensity: 1.0,
+            source: "test".to_string(),
+        });
+
+        let step = state.status_effect_step();
+        assert_eq!(step.sleep_change, -4.0 * 1.5 + -2.0 * 0.5);
+        assert_eq!(step.health_change, 0.0);
+    }
+
+    #[test]
+    fn maxed_out_stack_reports_nothing_applied() {
+        let mut state = GameState::default();
+        define(
+            &mut state,
+            StatusEffect::Anxious,
+            StackingMode::Stack,
+            1.0,
+            0.0,
+        );
+        let card = ActivityCard {
+            status_effects: vec![StatusEffectApplication {
+                effect: StatusEffect::Anxious,
+                duration: 2,
+                intensity: 1.0,
+            }],
+            ..card_costing(CardCosts::default())
+        };
+        let applied = |events: &[GameEvent]| {
+            events
+                .iter()
+                .filter(|event| matches!(event, GameEvent::StatusEffectApplied { .. }))
+                .count()
+        };
+
+        assert_eq!(applied(&state.apply_card_effects(&card, 1)), 1);
+        assert_eq!(applied(&state.apply_card_effects(&card, 2)), 0);
+        assert_eq!(state.status_effects.len(), 1);
+    }
+}
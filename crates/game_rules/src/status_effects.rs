@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{ResourceEffects, ResourceType, StatusEffect};
+
+/// What happens when an effect is applied while it is already active
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StackingMode {
+    /// The new application replaces the running one
+    #[default]
+    Refresh,
+    /// Each application runs alongside the others until their intensities reach the max
+    Stack,
+    /// The stronger intensity and the longer duration are kept
+    Max,
+}
+
+/// How a status effect plays, one entry of `status_effects.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEffectDefinition {
+    pub effect: StatusEffect,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Resource change every turn at intensity 1
+    #[serde(default)]
+    pub turn_drain: ResourceEffects,
+    /// Resources that can't recover while the effect is active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks_recovery: Vec<ResourceType>,
+    /// Added to the multiplier of card effects at intensity 1
+    #[serde(default)]
+    pub card_effect_multiplier: f32,
+    #[serde(default)]
+    pub stacking: StackingMode,
+    #[serde(default = "default_max_intensity")]
+    pub max_intensity: f32,
+    /// Counts towards the cascade failure ending
+    #[serde(default)]
+    pub cascading: bool,
+    /// Hex color of the effect in the status panel
+    pub color: String,
+    /// Image shown next to the name in the status panel
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+fn default_max_intensity() -> f32 {
+    1.0
+}
+
+/// All status effect definitions the rules apply
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StatusEffectDefinitions(pub Vec<StatusEffectDefinition>);
+
+impl StatusEffectDefinitions {
+    pub fn get(&self, effect: StatusEffect) -> Option<&StatusEffectDefinition> {
+        self.0.iter().find(|definition| definition.effect == effect)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::path::Path;
+
+use crate::cards::{StatusEffect, StatusEffectApplication};
+use crate::content::GameContent;
+
+/// Furniture in the room the character walks up to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Furniture {
+    Bed,
+    Kitchen,
+    ComputerDesk,
+    Sink,
+    GameConsole,
+    Phone,
+}
+
+/// Room objects the player can be sent to and the furniture the game navigates to for each,
+/// the one list both the content checks and the navigation go by
+pub const NAVIGABLE_OBJECTS: [(&str, Furniture); 8] = [
+    ("bed", Furniture::Bed),
+    ("kitchen", Furniture::Kitchen),
+    ("computer", Furniture::ComputerDesk),
+    ("fridge", Furniture::Kitchen),
+    ("phone", Furniture::Phone),
+    ("bathroom", Furniture::Sink),
+    ("tv", Furniture::GameConsole),
+    ("gameconsole", Furniture::GameConsole),
+];
+
+/// Furniture the room object `name` is at, if the game can navigate to it
+pub fn navigable_object(name: &str) -> Option<Furniture> {
+    NAVIGABLE_OBJECTS
+        .iter()
+        .find(|(object, _)| *object == name)
+        .map(|(_, furniture)| *furniture)
+}
+
+/// Something in the content the game would trip over at runtime
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentProblem {
+    /// What the problem was found in, e.g. `cards.json #12`
+    pub source: String,
+    pub message: String,
+}
+
+impl fmt::Display for ContentProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.source, self.message)
+    }
+}
+
+struct Problems(Vec<ContentProblem>);
+
+impl Problems {
+    fn push(&mut self, source: impl Into<String>, message: impl Into<String>) {
+        self.0.push(ContentProblem {
+            source: source.into(),
+            message: message.into(),
+        });
+    }
+
+    fn require_file(&mut self, assets_dir: &Path, source: &str, path: &str) {
+        if !assets_dir.join(path).is_file() {
+            self.push(source, format!("missing {path}"));
+        }
+    }
+
+    fn require_unique<T: Hash + Eq + fmt::Display>(
+        &mut self,
+        file: &str,
+        ids: impl IntoIterator<Item = T>,
+    ) {
+        let mut seen = HashSet::new();
+        for id in ids {
+            if seen.contains(&id) {
+                self.push(file, format!("duplicate id {id}"));
+            } else {
+                seen.insert(id);
+            }
+        }
+    }
+
+    fn require_definitions(
+        &mut self,
+        content: &GameContent,
+        source: &str,
+        applications: &[StatusEffectApplication],
+    ) {
+        for application in applications {
+            if content.status_effects.get(application.effect).is_none() {
+                self.push(
+                    source,
+                    format!(
+                        "{:?} has no entry in status_effects.json",
+                        application.effect
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Cross-check the content against itself and the files in the assets directory, the
+/// JSON itself is already known to parse since `content` was loaded from it
+pub fn validate_content(content: &GameContent, assets_dir: &Path) -> Vec<ContentProblem> {
+    let mut problems = Problems(Vec::new());
+
+    problems.require_unique(
+        "cards.json",
+        content.activity_cards.iter().map(|card| card.id),
+    );
+    problems.require_unique(
+        "schizophrenic-cards.json",
+        content.schizophrenic_cards.iter().map(|card| card.id),
+    );
+    problems.require_unique(
+        "endgame.json",
+        content
+            .endgame_scenarios
+            .iter()
+            .map(|scenario| &scenario.id),
+    );
+    problems.require_unique(
+        "status_effects.json",
+        content
+            .status_effects
+            .0
+            .iter()
+            .map(|definition| format!("{:?}", definition.effect)),
+    );
+
+    for card in &content.activity_cards {
+        let source = format!("cards.json #{} {}", card.id, card.name);
+        problems.require_file(assets_dir, &source, &format!("cards/card-{}.png", card.id));
+        // Only activity cards start cutscenes, adversary ids would overlap them anyway
+        problems.require_file(assets_dir, &source, &format!("scenarios/{}.rpy", card.id));
+
+        for object in card.conditions.required_objects.iter().flatten() {
+            if navigable_object(object).is_none() {
+                problems.push(&source, format!("unknown required object {object}"));
+            }
+        }
+        problems.require_definitions(content, &source, &card.status_effects);
+    }
+
+    let mut known_symptoms = HashSet::new();
+    for card in &content.schizophrenic_cards {
+        let source = format!("schizophrenic-cards.json #{} {}", card.id, card.title);
+        problems.require_file(
+            assets_dir,
+            &source,
+            &format!("schizo_cards/card-{}.png", card.id),
+        );
+        problems.require_definitions(content, &source, &card.effects.status_effects);
+        known_symptoms.extend(card.conditions.trigger_symptoms.iter().map(String::as_str));
+    }
+
+    for scenario in &content.endgame_scenarios {
+        let source = format!("endgame.json {}", scenario.id);
+        problems.require_file(assets_dir, &source, &format!("endgame/{}.rpy", scenario.id));
+        problems.require_file(assets_dir, &source, &format!("{}.png", scenario.id));
+
+        let conditions = &scenario.trigger_conditions;
+        if conditions.is_empty() && scenario.results.is_empty() {
+            problems.push(
+                &source,
+                "no trigger conditions or results, it can never be reached",
+            );
+        }
+        for name in conditions.status_effects_present.iter().flatten() {
+            if !StatusEffect::ALL
+                .iter()
+                .any(|effect| effect.matches_name(name))
+            {
+                problems.push(&source, format!("{name} is not a status effect"));
+            }
+        }
+        for symptom in conditions.active_trigger_symptoms.iter().flatten() {
+            if !known_symptoms.contains(symptom.as_str()) {
+                problems.push(
+                    &source,
+                    format!("{symptom} is not a trigger symptom of any adversary card"),
+                );
+            }
+        }
+    }
+
+    for effect in StatusEffect::ALL {
+        if content.status_effects.get(effect).is_none() {
+            problems.push("status_effects.json", format!("{effect:?} is not defined"));
+        }
+    }
+    for definition in &content.status_effects.0 {
+        let source = format!("status_effects.json {:?}", definition.effect);
+        if !is_hex_color(&definition.color) {
+            problems.push(&source, format!("{} is not a hex color", definition.color));
+        }
+        if let Some(icon) = &definition.icon {
+            problems.require_file(assets_dir, &source, icon);
+        }
+    }
+
+    problems.0
+}
+
+/// Same forms the game accepts: `#` followed by 3, 4, 6 or 8 hex digits, the `#` being optional
+fn is_hex_color(color: &str) -> bool {
+    let digits = color.strip_prefix('#').unwrap_or(color);
+    matches!(digits.len(), 3 | 4 | 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endgame::EndGameScenario;
+
+    fn scenario(id: &str, mut scenario: serde_json::Value) -> EndGameScenario {
+        for key in ["id", "title", "description", "image_prompt", "ending_text"] {
+            scenario[key] = serde_json::json!(id);
+        }
+        serde_json::from_value(scenario).unwrap()
+    }
+
+    fn messages(content: &GameContent) -> Vec<String> {
+        validate_content(content, Path::new("/nonexistent"))
+            .into_iter()
+            .filter(|problem| !problem.message.starts_with("missing "))
+            .filter(|problem| problem.source.starts_with("endgame.json"))
+            .map(|problem| problem.message)
+            .collect()
+    }
+
+    #[test]
+    fn status_effects_must_be_named_in_full() {
+        let content = GameContent {
+            endgame_scenarios: vec![scenario(
+                "exhaustion",
+                serde_json::json!({
+                    "trigger_conditions": {
+                        "status_effects_present": ["exhausted", "Sick", "ex", "s"]
+                    }
+                }),
+            )],
+            ..Default::default()
+        };
+        assert_eq!(
+            messages(&content),
+            ["ex is not a status effect", "s is not a status effect"]
+        );
+    }
+
+    #[test]
+    fn scenario_has_to_be_reachable() {
+        let content = GameContent {
+            endgame_scenarios: vec![
+                scenario("nowhere", serde_json::json!({ "trigger_conditions": {} })),
+                scenario(
+                    "recovery",
+                    serde_json::json!({
+                        "results": [{ "Success": "Recovery" }],
+                        "trigger_conditions": {}
+                    }),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            messages(&content),
+            ["no trigger conditions or results, it can never be reached"]
+        );
+    }
+
+    #[test]
+    fn navigable_objects_are_looked_up_by_name() {
+        assert_eq!(navigable_object("fridge"), Some(Furniture::Kitchen));
+        assert_eq!(navigable_object("tv"), Some(Furniture::GameConsole));
+        assert_eq!(navigable_object("window"), None);
+    }
+}
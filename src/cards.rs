@@ -7,22 +7,30 @@ use bevy_defer::AsyncCommandsExtension;
 use bevy_defer::AsyncWorld;
 use bevy_la_mesa::CardMetadata;
 use bevy_la_mesa::events::CardHoverable;
-use bevy_la_mesa::events::{CardPress, DeckShuffle, DrawToHand, PlaceCardOnTable, RenderDeck};
+use bevy_la_mesa::events::{CardPress, DrawToHand, PlaceCardOnTable, RenderDeck};
 use bevy_la_mesa::{Card, CardOnTable, Hand, PlayArea};
 use bevy_la_mesa::{DeckArea, HandArea};
 use bevy_tweening::Animator;
 use bevy_tweening::Tween;
 use bevy_tweening::lens::TransformPositionLens;
-use serde::{Deserialize, Deserializer, Serialize};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub use game_rules::{
+    ActivityCard, AdditionalCost, CardAvailability, CardConditions, CardCosts, CardType,
+    CrisisLevel, LockKind, MULLIGAN_MENTAL_COST, Mood, ResourceEffects, ResourceType,
+    SchizophrenicCard, SpectrumConditions, StatusEffect, StatusEffectApplication,
+    StatusEffectDefinition, StatusEffectDefinitions, TimeOfDay, starting_deck,
+};
 
 use crate::AppState;
 use crate::logic::AdversaryCardDrawnEvent;
 use crate::logic::CardSelectionSuccess;
 use crate::logic::CutsceneEndEvent;
 use crate::logic::CutsceneStartEvent;
-use crate::logic::GameState;
-use crate::logic::PhaseChangedEvent;
-use crate::logic::{CardDrawnEvent, CardSelectedEvent, GamePhase, GamePhaseState};
+use crate::logic::{CardSelectedEvent, GameAction, GameActionEvent, GamePhase, GameRng, GameRules};
+use crate::logic::{CardsDiscardedEvent, CardsDrawnEvent, MulliganEvent};
+use crate::ui::ToggleMulliganEvent;
 
 /// Plugin that handles all card-related functionality
 pub struct CardSystemPlugin;
@@ -40,6 +48,7 @@ pub struct DragCardsInHandUp {
 impl Plugin for CardSystemPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::Game), setup)
+            .init_resource::<MulliganSelection>()
             .add_event::<DragCardsInHandDown>()
             .add_event::<DragCardsInHandUp>()
             .add_systems(
@@ -47,14 +56,19 @@ impl Plugin for CardSystemPlugin {
                 (
                     init_player_deck,
                     handle_player_card_draw_phase.after(init_player_deck),
+                    deal_drawn_cards,
+                    remove_discarded_cards,
                     init_adversary_deck,
                     handle_adversary_card_draw_phase.after(init_adversary_deck),
                     handle_card_selection_attempt,
                     handle_card_selection_success,
+                    handle_toggle_mulligan,
+                    mark_blocked_cards,
                     handle_cutscene_start,
                     handle_cutscene_end,
                     handle_drag_cards_in_hand_down,
                     handle_drag_cards_in_hand_up,
+                    refresh_cards_in_play,
                 )
                     .run_if(in_state(AppState::Game)),
             );
@@ -195,15 +209,21 @@ fn setup(
     ));
 }
 
+/// A new run starts out on the starting deck, the other cards are bought between days
 fn init_player_deck(
-    mut ew_render_deck: EventWriter<RenderDeck<GameCard>>,
-    q_decks: Query<(Entity, &DeckArea)>,
     activity_cards_handle: Option<Res<ActivityCardsHandle>>,
     activity_cards_assets: Res<Assets<ActivityCards>>,
-    phase_state: Res<GamePhaseState>,
-    game_state: Res<GameState>,
+    rules: Res<GameRules>,
+    mut game_rng: ResMut<GameRng>,
+    mut game_actions: EventWriter<GameActionEvent>,
+    mut last_turn: Local<u32>,
 ) {
-    if phase_state.current_phase != GamePhase::CardDraw {
+    if rules.phase.current_phase != GamePhase::CardDraw || !rules.state.deck.is_empty() {
+        return;
+    }
+
+    // The deck is built before the draw of this turn is applied
+    if *last_turn == rules.phase.turn_number {
         return;
     }
 
@@ -213,27 +233,21 @@ fn init_player_deck(
     };
 
     if let Some(activity_cards) = activity_cards_assets.get(activity_cards_handle.id()) {
-        // Filter to only activity cards
-        let game_cards = GameCard::from_activity_cards(activity_cards.0.clone());
-        let available_cards = game_state.filter_cards(&game_cards);
-
-        if let Some((deck_entity, _)) = q_decks.iter().find(|(_, deck)| deck.marker == 1) {
-            ew_render_deck.write(RenderDeck::<GameCard> {
-                deck_entity,
-                deck: available_cards,
-            });
-        }
+        *last_turn = rules.phase.turn_number;
+        game_actions.write(GameActionEvent(GameAction::NewDeck {
+            cards: starting_deck(activity_cards),
+            shuffle_seed: game_rng.random(),
+        }));
     }
 }
 
 fn handle_player_card_draw_phase(
-    mut commands: Commands,
-    phase_state: Res<GamePhaseState>,
-    q_decks: Query<(Entity, &DeckArea)>,
-    q_cards_on_table: Query<(Entity, &Card<GameCard>, &CardOnTable)>,
+    rules: Res<GameRules>,
+    mut game_rng: ResMut<GameRng>,
     mut last_turn: Local<u32>,
-    mut phase_changed_events: EventWriter<PhaseChangedEvent>,
+    mut game_actions: EventWriter<GameActionEvent>,
 ) {
+    let phase_state = &rules.phase;
     // Only trigger when we enter the CardDraw phase
     if phase_state.current_phase != GamePhase::CardDraw {
         return;
@@ -242,38 +256,60 @@ fn handle_player_card_draw_phase(
     if *last_turn == phase_state.turn_number {
         return;
     }
-    if let Some((deck_entity, _)) = q_decks.iter().find(|(_, deck)| deck.marker == 1) {
-        let n_cards_on_table = q_cards_on_table.iter().len();
-        let cards_to_draw = 5 - n_cards_on_table;
-        if cards_to_draw <= 0 {
-            warn!("No cards to draw, table is full");
-            return;
+
+    // The rules fill the hand up from the deck, see `deal_drawn_cards`
+    *last_turn = phase_state.turn_number;
+    game_actions.write(GameActionEvent(GameAction::DrawCards {
+        shuffle_seed: game_rng.random(),
+    }));
+}
+
+/// Deal the cards the rules drew, the hand kept from the last turn stays where it is
+fn deal_drawn_cards(
+    mut commands: Commands,
+    mut cards_drawn_events: EventReader<CardsDrawnEvent>,
+    mut ew_render_deck: EventWriter<RenderDeck<GameCard>>,
+    q_decks: Query<(Entity, &DeckArea)>,
+) {
+    for CardsDrawnEvent(cards) in cards_drawn_events.read() {
+        if cards.is_empty() {
+            continue;
         }
-        *last_turn = phase_state.turn_number;
+        let Some((deck_entity, _)) = q_decks.iter().find(|(_, deck)| deck.marker == 1) else {
+            warn!("No player deck found for card draw");
+            continue;
+        };
+
+        let num_cards = cards.len();
+        ew_render_deck.write(RenderDeck::<GameCard> {
+            deck_entity,
+            deck: GameCard::from_activity_cards(cards.clone()),
+        });
         // Draw cards after a delay
         commands.spawn_task(move || async move {
-            AsyncWorld.sleep(1.0).await;
-            // Shuffle the deck first
-            AsyncWorld.send_event(DeckShuffle {
-                deck_entity,
-                duration: 50,
-            })?;
-            AsyncWorld.sleep(2.0).await;
-            // Send draw event
+            AsyncWorld.sleep(3.0).await;
             AsyncWorld.send_event(DrawToHand {
                 deck_entity,
-                num_cards: cards_to_draw,
+                num_cards,
                 player: 1,
             })?;
-            // Send phase event
-            AsyncWorld.send_event(CardDrawnEvent {
-                card_count: cards_to_draw,
-            })?;
             Ok(())
         });
-        phase_changed_events.write(PhaseChangedEvent {
-            new_phase: GamePhase::CardSelection,
-        });
+    }
+}
+
+/// Cards the rules discarded from the hand leave it
+fn remove_discarded_cards(
+    mut commands: Commands,
+    mut cards_discarded_events: EventReader<CardsDiscardedEvent>,
+    q_cards: Query<(Entity, &Card<GameCard>, &Hand)>,
+) {
+    for CardsDiscardedEvent(cards) in cards_discarded_events.read() {
+        for (entity, card, hand) in q_cards.iter() {
+            if hand.player == 1 && cards.iter().any(|discarded| discarded.id == card.data.id()) {
+                commands.entity(entity).despawn();
+            }
+        }
     }
 }
 
@@ -282,9 +318,15 @@ fn init_adversary_deck(
     q_decks: Query<(Entity, &DeckArea)>,
     schizophrenic_cards_handle: Option<Res<SchizophrenicCardsHandle>>,
     schizophrenic_cards_assets: Res<Assets<SchizophrenicCards>>,
-    phase_state: Res<GamePhaseState>,
+    rules: Res<GameRules>,
+    mut game_rng: ResMut<GameRng>,
+    mut last_turn: Local<u32>,
 ) {
-    if phase_state.current_phase != GamePhase::AdversaryCardDraw {
+    if rules.phase.current_phase != GamePhase::AdversaryCardDraw {
+        return;
+    }
+
+    if *last_turn == rules.phase.turn_number {
         return;
     }
 
@@ -296,8 +338,12 @@ fn init_adversary_deck(
     if let Some(schizophrenic_cards) =
         schizophrenic_cards_assets.get(schizophrenic_cards_handle.id())
     {
-        let game_cards = GameCard::from_schizophrenic_cards(schizophrenic_cards.0.clone());
+        // Only cards whose conditions hold can be dealt, weighted by the hidden stats
+        let dealt_cards = rules.deal_adversary_cards(&schizophrenic_cards.0, &mut **game_rng);
+        let game_cards = GameCard::from_schizophrenic_cards(dealt_cards);
+
         if let Some((deck_entity, _)) = q_decks.iter().find(|(_, deck)| deck.marker == 2) {
+            *last_turn = rules.phase.turn_number;
             ew_render_deck.write(RenderDeck::<GameCard> {
                 deck_entity,
                 deck: game_cards,
@@ -308,12 +354,16 @@ fn init_adversary_deck(
 
 fn handle_adversary_card_draw_phase(
     mut commands: Commands,
-    phase_state: Res<GamePhaseState>,
+    rules: Res<GameRules>,
     q_decks: Query<(Entity, &DeckArea)>,
     q_cards_on_table: Query<(Entity, &Card<GameCard>, &CardOnTable)>,
+    schizophrenic_cards_handle: Option<Res<SchizophrenicCardsHandle>>,
+    schizophrenic_cards_assets: Res<Assets<SchizophrenicCards>>,
     mut last_turn: Local<u32>,
-    mut phase_changed_events: EventWriter<PhaseChangedEvent>,
+    mut game_actions: EventWriter<GameActionEvent>,
 ) {
+    let phase_state = &rules.phase;
+
     // Only trigger when we enter the AdversaryCardDraw phase
     if phase_state.current_phase != GamePhase::AdversaryCardDraw {
         return;
@@ -334,24 +384,35 @@ fn handle_adversary_card_draw_phase(
             return;
         }
 
+        let Some(schizophrenic_cards) = schizophrenic_cards_handle
+            .and_then(|handle| schizophrenic_cards_assets.get(handle.id()))
+        else {
+            return;
+        };
+
         *last_turn = phase_state.turn_number;
 
+        // The deck only holds eligible cards, which may be fewer than a full hand
+        let num_cards = rules
+            .eligible_adversary_cards(&schizophrenic_cards.0)
+            .len()
+            .min(cards_to_draw + 1);
+        game_actions.write(GameActionEvent(GameAction::DrawAdversaryCards {
+            count: num_cards,
+        }));
+        if num_cards == 0 {
+            return;
+        }
+
         // Draw cards after a delay
         commands.spawn_task(move || async move {
-            AsyncWorld.sleep(1.0).await;
-
-            // Shuffle the deck first
-            AsyncWorld.send_event(DeckShuffle {
-                deck_entity,
-                duration: 50,
-            })?;
-
-            AsyncWorld.sleep(2.0).await;
+            // The deck was shuffled when rendered
+            AsyncWorld.sleep(3.0).await;
 
             // Send draw event for adversary (player 2)
             AsyncWorld.send_event(DrawToHand {
                 deck_entity,
-                num_cards: cards_to_draw + 1,
+                num_cards,
                 player: 2,
             })?;
 
@@ -362,27 +423,255 @@ fn handle_adversary_card_draw_phase(
 
             Ok(())
         });
-
-        phase_changed_events.write(PhaseChangedEvent {
-            new_phase: GamePhase::AdversaryCardSelection,
-        });
     } else {
         warn!("No adversary deck found for card draw");
     }
 }
 
+/// Carries edits of `cards.json` and `schizophrenic-cards.json` over to the cards already
+/// dealt. Cards in the player's hand that were removed leave it.
+fn refresh_cards_in_play(
+    mut commands: Commands,
+    activity_cards_handle: Option<Res<ActivityCardsHandle>>,
+    activity_cards_assets: Res<Assets<ActivityCards>>,
+    schizophrenic_cards_handle: Option<Res<SchizophrenicCardsHandle>>,
+    schizophrenic_cards_assets: Res<Assets<SchizophrenicCards>>,
+    mut activity_card_events: EventReader<AssetEvent<ActivityCards>>,
+    mut schizophrenic_card_events: EventReader<AssetEvent<SchizophrenicCards>>,
+    mut rules: ResMut<GameRules>,
+    mut q_cards: Query<(Entity, &mut Card<GameCard>, Option<&Hand>)>,
+) {
+    let activity_cards = activity_cards_handle
+        .filter(|handle| {
+            activity_card_events
+                .read()
+                .any(|event| event.is_modified(handle.id()))
+        })
+        .and_then(|handle| activity_cards_assets.get(handle.id()));
+    let schizophrenic_cards = schizophrenic_cards_handle
+        .filter(|handle| {
+            schizophrenic_card_events
+                .read()
+                .any(|event| event.is_modified(handle.id()))
+        })
+        .and_then(|handle| schizophrenic_cards_assets.get(handle.id()));
+
+    if activity_cards.is_none() && schizophrenic_cards.is_none() {
+        return;
+    }
+    if let Some(activity_cards) = activity_cards {
+        rules.state.deck.refresh(activity_cards);
+    }
+
+    for (entity, mut card, hand) in q_cards.iter_mut() {
+        let in_player_hand = hand.is_some_and(|hand| hand.player == 1);
+
+        match &card.data.card_variant {
+            CardVariant::Activity(activity_card) => {
+                let Some(activity_cards) = activity_cards else {
+                    continue;
+                };
+                let Some(updated) = activity_cards.iter().find(|c| c.id == activity_card.id) else {
+                    if in_player_hand {
+                        info!(
+                            "Card {} was removed, taking it out of the hand",
+                            activity_card.name
+                        );
+                        commands.entity(entity).despawn();
+                    }
+                    continue;
+                };
+
+                card.data.card_variant = CardVariant::Activity(updated.clone());
+            }
+            CardVariant::Schizophrenic(schizophrenic_card) => {
+                if let Some(updated) = schizophrenic_cards
+                    .and_then(|cards| cards.iter().find(|c| c.id == schizophrenic_card.id))
+                {
+                    card.data.card_variant = CardVariant::Schizophrenic(updated.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Tint of the hand cards that can't be played right now
+const BLOCKED_CARD_TINT: Color = Color::srgb(0.35, 0.35, 0.35);
+
+/// Hand card the rules won't let the player play right now, and why
+#[derive(Component)]
+pub struct BlockedCard {
+    pub blocking_conditions: Vec<String>,
+    /// Materials of the card meshes before they were tinted, put back once it can be played
+    original_materials: Vec<(Entity, Handle<StandardMaterial>)>,
+}
+
+/// Grey out the cards in the player's hand that can't be played and bring back the ones that
+/// can again, whenever the rules or the hand change
+fn mark_blocked_cards(
+    mut commands: Commands,
+    rules: Res<GameRules>,
+    q_cards: Query<(Entity, &Card<GameCard>, &Hand, Option<&BlockedCard>)>,
+    q_hand_changed: Query<(), Or<(Added<Hand>, Changed<Card<GameCard>>)>>,
+    q_children: Query<&Children>,
+    q_materials: Query<&MeshMaterial3d<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes_pending: Local<bool>,
+) {
+    if !rules.is_changed() && q_hand_changed.is_empty() && !*meshes_pending {
+        return;
+    }
+    *meshes_pending = false;
+
+    for (entity, card, hand, blocked) in q_cards.iter() {
+        let Some(activity_card) = card.data.to_activity_card() else {
+            continue;
+        };
+        if hand.player != 1 {
+            continue;
+        }
+
+        let (can_play, blocking_conditions) = rules.can_play_card(activity_card);
+        match blocked {
+            None if can_play => {}
+            Some(blocked) if can_play => {
+                for (mesh, material) in &blocked.original_materials {
+                    if let Ok(mut mesh) = commands.get_entity(*mesh) {
+                        mesh.insert(MeshMaterial3d(material.clone()));
+                    }
+                }
+                commands.entity(entity).remove::<BlockedCard>();
+            }
+            Some(blocked) => {
+                if blocked.blocking_conditions != blocking_conditions {
+                    commands.entity(entity).insert(BlockedCard {
+                        blocking_conditions,
+                        original_materials: blocked.original_materials.clone(),
+                    });
+                }
+            }
+            None => {
+                let original_materials = tint_card(
+                    &mut commands,
+                    entity,
+                    BLOCKED_CARD_TINT,
+                    &q_children,
+                    &q_materials,
+                    &mut materials,
+                );
+                // The card meshes may not be spawned yet, then this is tried again next frame
+                if original_materials.is_empty() {
+                    *meshes_pending = true;
+                    continue;
+                }
+                commands.entity(entity).insert(BlockedCard {
+                    blocking_conditions,
+                    original_materials,
+                });
+            }
+        }
+    }
+}
+
+/// Give every mesh of the card its own copy of its material in `color`, returns the materials
+/// the meshes had before
+fn tint_card(
+    commands: &mut Commands,
+    entity: Entity,
+    color: Color,
+    q_children: &Query<&Children>,
+    q_materials: &Query<&MeshMaterial3d<StandardMaterial>>,
+    materials: &mut Assets<StandardMaterial>,
+) -> Vec<(Entity, Handle<StandardMaterial>)> {
+    let mut original_materials = Vec::new();
+    for descendant in q_children.iter_descendants(entity) {
+        let Ok(original) = q_materials.get(descendant) else {
+            continue;
+        };
+        let Some(material) = materials.get(&original.0) else {
+            continue;
+        };
+
+        let mut material = material.clone();
+        material.base_color = color;
+        commands
+            .entity(descendant)
+            .insert(MeshMaterial3d(materials.add(material)));
+        original_materials.push((descendant, original.0.clone()));
+    }
+    original_materials
+}
+
+/// How far a card marked for the mulligan sticks out of the hand
+const MULLIGAN_MARK_OFFSET: Vec3 = Vec3::new(0.0, 0.0, -1.0);
+
+/// Hand cards marked to be redrawn, while marking a pressed card is marked instead of played
+#[derive(Resource, Default)]
+pub struct MulliganSelection {
+    pub marking: bool,
+    pub card_ids: Vec<u32>,
+}
+
+fn tween_card(commands: &mut Commands, entity: Entity, start: Vec3, end: Vec3) {
+    let tween = Tween::new(
+        EaseFunction::CubicIn,
+        Duration::from_millis(200),
+        TransformPositionLens { start, end },
+    );
+    commands.entity(entity).insert(Animator::new(tween));
+}
+
+/// First press starts marking cards, the second redraws the marked ones, or cancels when
+/// none were marked
+fn handle_toggle_mulligan(
+    mut commands: Commands,
+    mut toggle_mulligan_events: EventReader<ToggleMulliganEvent>,
+    mut ew_mulligan: EventWriter<MulliganEvent>,
+    mut selection: ResMut<MulliganSelection>,
+    rules: Res<GameRules>,
+    q_cards: Query<(Entity, &Card<GameCard>, &Hand, &Transform)>,
+) {
+    let mut finished = selection.marking && rules.phase.current_phase != GamePhase::CardSelection;
+    for _ in toggle_mulligan_events.read() {
+        if !selection.marking {
+            selection.marking = true;
+            continue;
+        }
+
+        if !selection.card_ids.is_empty() {
+            ew_mulligan.write(MulliganEvent(selection.card_ids.clone()));
+        }
+        finished = true;
+    }
+
+    if !finished {
+        return;
+    }
+
+    // Marked cards that stay in the hand go back in line
+    for (entity, card, hand, transform) in q_cards.iter() {
+        if hand.player == 1 && selection.card_ids.contains(&card.data.id()) {
+            let start = transform.translation;
+            tween_card(&mut commands, entity, start, start - MULLIGAN_MARK_OFFSET);
+        }
+    }
+    *selection = MulliganSelection::default();
+}
+
 /// Handle placing card on table after selection
 pub fn handle_card_selection_attempt(
+    mut commands: Commands,
     mut card_press: EventReader<CardPress>,
     mut ew_card_selected: EventWriter<CardSelectedEvent>,
-    phase_state: Res<GamePhaseState>,
+    mut mulligan_selection: ResMut<MulliganSelection>,
+    rules: Res<GameRules>,
     mut q_cards: ParamSet<(
         Query<(Entity, &Card<GameCard>, &CardOnTable)>,
-        Query<(Entity, &Card<GameCard>, &Hand)>,
+        Query<(Entity, &Card<GameCard>, &Hand, &Transform)>,
     )>,
 ) {
     // Only place cards during CharacterAction phase
-    if phase_state.current_phase != GamePhase::CardSelection {
+    if rules.phase.current_phase != GamePhase::CardSelection {
         return;
     }
 
@@ -396,7 +685,26 @@ pub fn handle_card_selection_attempt(
         }
 
         let p1 = q_cards.p1();
-        if let Ok((_, card, _)) = p1.get(event.entity)
+        if mulligan_selection.marking {
+            if let Ok((entity, card, _, transform)) = p1.get(event.entity) {
+                let card_id = card.data.id();
+                let start = transform.translation;
+                if let Some(index) = mulligan_selection
+                    .card_ids
+                    .iter()
+                    .position(|id| *id == card_id)
+                {
+                    mulligan_selection.card_ids.remove(index);
+                    tween_card(&mut commands, entity, start, start - MULLIGAN_MARK_OFFSET);
+                } else {
+                    mulligan_selection.card_ids.push(card_id);
+                    tween_card(&mut commands, entity, start, start + MULLIGAN_MARK_OFFSET);
+                }
+            }
+            continue;
+        }
+
+        if let Ok((_, card, _, _)) = p1.get(event.entity)
             && n_cards_on_table < 1
         {
             println!("selected activity card {:?}", card.data);
@@ -419,18 +727,16 @@ pub fn handle_card_selection_success(
     )>,
 ) {
     for event in er_card_selction_success.read() {
-        let selected_card_entity = q_cards
-            .p1()
-            .iter()
-            .find(|(_, card, _)| {
-                if let CardVariant::Activity(activity_card) = &card.data.card_variant {
-                    activity_card.id == event.0.id
-                } else {
-                    false
-                }
-            })
-            .unwrap()
-            .0;
+        // A hot reload may have despawned the hand since the card was picked
+        let Some((selected_card_entity, _, _)) = q_cards.p1().iter().find(|(_, card, _)| {
+            if let CardVariant::Activity(activity_card) = &card.data.card_variant {
+                activity_card.id == event.0.id
+            } else {
+                false
+            }
+        }) else {
+            continue;
+        };
 
         let n_cards_on_table = q_cards.p0().iter().len();
 
@@ -492,196 +798,6 @@ pub enum CardVariant {
     Schizophrenic(SchizophrenicCard),
 }
 
-// Activity Card Types
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct ActivityCard {
-    pub id: u32,
-    pub name: String,
-    pub description: String,
-    pub category: String,
-    pub conditions: CardConditions,
-    pub costs: CardCosts,
-    pub effects: ResourceEffects,
-    pub status_effects: Vec<StatusEffectApplication>,
-    pub card_type: CardType,
-    pub availability: CardAvailability,
-    pub flavor_text: String,
-    pub one_time_use: bool,
-    pub cooldown: Option<u32>,
-}
-
-// Schizophrenic Card Types
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SchizophrenicCard {
-    pub id: u32,
-    pub card_name: String,
-    pub scenario_id: String,
-    pub title: String,
-    pub conditions: SpectrumConditions,
-    pub setup: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct CardConditions {
-    pub min_sleep: Option<f32>,
-    pub min_health: Option<f32>,
-    pub min_mental: Option<f32>,
-    pub min_food: Option<f32>,
-    pub max_sleep: Option<f32>,
-    pub max_health: Option<f32>,
-    pub max_mental: Option<f32>,
-    pub max_food: Option<f32>,
-    pub required_mood: Option<Mood>,
-    pub forbidden_mood: Option<Mood>,
-    pub time_of_day: Option<Vec<TimeOfDay>>,
-    pub day_range: Option<(u32, u32)>,
-    pub required_objects: Option<Vec<String>>,
-    pub crisis_level: Option<CrisisLevel>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct CardCosts {
-    pub sleep_cost: f32,
-    pub health_cost: f32,
-    pub mental_cost: f32,
-    pub food_cost: f32,
-    pub time_cost: f32,
-    pub additional_costs: Vec<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct ResourceEffects {
-    pub sleep: f32,
-    pub health: f32,
-    pub mental: f32,
-    pub food: f32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StatusEffectApplication {
-    pub effect: StatusEffect,
-    pub duration: u32,
-    pub intensity: f32,
-}
-
-// Conditions specific to spectrum cards
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct SpectrumConditions {
-    pub trigger_symptoms: Vec<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
-pub enum CardType {
-    #[default]
-    BasicNeed,
-    Entertainment,
-    Social,
-    Crisis,
-    TimeSpecific,
-    Delusion,
-    LLMGenerated,
-    ThoughtCard,
-    MemoryCard,
-    ImpulseCard,
-    ComboCard,
-    Spectrum, // New type for schizophrenic cards
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
-pub enum CardAvailability {
-    #[default]
-    Always,
-    ConditionalOnly,
-    CrisisOnly,
-    LLMOnly,
-    OneTime,
-    DailyReset,
-}
-
-#[derive(Debug, Clone, Serialize, PartialEq)]
-pub enum StatusEffect {
-    Insomnia(u32),
-    Sick(u32),
-    Motivated(u32),
-    Overwhelmed(u32),
-    Addicted(String, u32),
-    Exhausted(u32),
-    Anxious(u32),
-    Depressed(u32),
-    Manic(u32),
-    Stable(u32),
-    Focused(u32),
-    Hungry(u32),
-}
-
-impl<'de> Deserialize<'de> for StatusEffect {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-
-        // Parse the string and create the appropriate enum variant with default duration
-        match s.as_str() {
-            "Insomnia" => Ok(StatusEffect::Insomnia(0)),
-            "Sick" => Ok(StatusEffect::Sick(0)),
-            "Motivated" => Ok(StatusEffect::Motivated(0)),
-            "Overwhelmed" => Ok(StatusEffect::Overwhelmed(0)),
-            "Exhausted" => Ok(StatusEffect::Exhausted(0)),
-            "Anxious" => Ok(StatusEffect::Anxious(0)),
-            "Depressed" => Ok(StatusEffect::Depressed(0)),
-            "Manic" => Ok(StatusEffect::Manic(0)),
-            "Stable" => Ok(StatusEffect::Stable(0)),
-            "Focused" => Ok(StatusEffect::Focused(0)),
-            "Hungry" => Ok(StatusEffect::Hungry(0)),
-            s if s.starts_with("Addicted") => {
-                // Handle "Addicted" or "Addicted(substance)" format
-                Ok(StatusEffect::Addicted("generic".to_string(), 0))
-            }
-            _ => Err(serde::de::Error::custom(format!(
-                "Unknown status effect: {s}"
-            ))),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum Mood {
-    Depressed,
-    Anxious,
-    Tired,
-    Neutral,
-    Content,
-    Manic,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum TimeOfDay {
-    EarlyMorning,
-    Morning,
-    Afternoon,
-    Evening,
-    Night,
-    LateNight,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-pub enum CrisisLevel {
-    None,
-    Mild,
-    Moderate,
-    Severe,
-    Critical,
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub enum ResourceType {
-    Sleep,
-    Health,
-    Mental,
-    Food,
-}
-
 #[derive(Deserialize, Asset, TypePath, Deref, DerefMut)]
 pub struct ActivityCards(pub Vec<ActivityCard>);
 
@@ -1,15 +1,12 @@
 use std::collections::HashMap;
 
 use bevy::prelude::*;
-use bevy_novel::{
-    NovelText,
-    events::{EventNovelEnd, EventStartScenario},
-    rpy_asset_loader::Rpy,
-};
+use bevy_novel::{NovelText, events::EventNovelEnd, rpy_asset_loader::Rpy};
 
 use crate::{
     AppState,
-    endgame::EndGameEvent,
+    cards::{ActivityCards, ActivityCardsHandle},
+    endgame::{EndGameScenarios, EndGameScenariosHandle},
     logic::{CutsceneEndEvent, CutsceneStartEvent},
 };
 
@@ -18,111 +15,81 @@ pub struct CutscenePlugin;
 impl Plugin for CutscenePlugin {
     fn build(&self, app: &mut App) {
         // Initialize app state and resources
-        app.add_systems(
-            OnEnter(AppState::Game),
-            (load_scenario, load_endgame_scenario),
-        )
-        .add_systems(
-            Update,
-            (start_visual_novel, handle_novel_end, handle_game_over)
-                .run_if(in_state(AppState::Game)),
-        );
+        app.init_resource::<ScenarioHandle>()
+            .init_resource::<EndGameScenarioHandle>()
+            // Not tied to a state, content loads during the splashscreen and can change any time
+            .add_systems(Update, (load_scenario, load_endgame_scenario))
+            .add_systems(
+                Update,
+                (start_visual_novel, handle_novel_end).run_if(in_state(AppState::Game)),
+            );
     }
 }
 
-#[derive(Resource, Deref, DerefMut)]
-pub struct ScenarioHandle(Vec<Handle<Rpy>>);
+/// Cutscene of each activity card by card id, `scenarios/{id}.rpy`
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ScenarioHandle(HashMap<u32, Handle<Rpy>>);
+
+/// Keeps a cutscene handle for every card in `cards.json`, so cards added while the game
+/// runs get theirs too
+fn load_scenario(
+    mut scenario_handle: ResMut<ScenarioHandle>,
+    asset_server: Res<AssetServer>,
+    activity_cards_handle: Option<Res<ActivityCardsHandle>>,
+    activity_cards_assets: Res<Assets<ActivityCards>>,
+    mut asset_events: EventReader<AssetEvent<ActivityCards>>,
+) {
+    let Some(activity_cards_handle) = activity_cards_handle else {
+        return;
+    };
+    if !asset_events.read().any(|event| {
+        event.is_loaded_with_dependencies(activity_cards_handle.id())
+            || event.is_modified(activity_cards_handle.id())
+    }) {
+        return;
+    }
+    let Some(activity_cards) = activity_cards_assets.get(activity_cards_handle.id()) else {
+        return;
+    };
 
-fn load_scenario(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let scenario_handle = ScenarioHandle(vec![
-        asset_server.load("scenarios/0.rpy"),
-        asset_server.load("scenarios/1.rpy"),
-        asset_server.load("scenarios/2.rpy"),
-        asset_server.load("scenarios/3.rpy"),
-        asset_server.load("scenarios/4.rpy"),
-        asset_server.load("scenarios/5.rpy"),
-        asset_server.load("scenarios/6.rpy"),
-        asset_server.load("scenarios/7.rpy"),
-        asset_server.load("scenarios/8.rpy"),
-        asset_server.load("scenarios/9.rpy"),
-        asset_server.load("scenarios/10.rpy"),
-        asset_server.load("scenarios/11.rpy"),
-        asset_server.load("scenarios/12.rpy"),
-        asset_server.load("scenarios/13.rpy"),
-        asset_server.load("scenarios/14.rpy"),
-        asset_server.load("scenarios/15.rpy"),
-        asset_server.load("scenarios/16.rpy"),
-        asset_server.load("scenarios/17.rpy"),
-        asset_server.load("scenarios/18.rpy"),
-        asset_server.load("scenarios/19.rpy"),
-        asset_server.load("scenarios/20.rpy"),
-        asset_server.load("scenarios/21.rpy"),
-        asset_server.load("scenarios/22.rpy"),
-        asset_server.load("scenarios/23.rpy"),
-        asset_server.load("scenarios/24.rpy"),
-        asset_server.load("scenarios/25.rpy"),
-        asset_server.load("scenarios/26.rpy"),
-        asset_server.load("scenarios/27.rpy"),
-        asset_server.load("scenarios/28.rpy"),
-        asset_server.load("scenarios/29.rpy"),
-        asset_server.load("scenarios/30.rpy"),
-        asset_server.load("scenarios/31.rpy"),
-        asset_server.load("scenarios/32.rpy"),
-        asset_server.load("scenarios/33.rpy"),
-        asset_server.load("scenarios/34.rpy"),
-    ]);
-    commands.insert_resource(scenario_handle);
+    scenario_handle.retain(|id, _| activity_cards.iter().any(|card| card.id == *id));
+    for card in activity_cards.iter() {
+        scenario_handle
+            .entry(card.id)
+            .or_insert_with(|| asset_server.load(format!("scenarios/{}.rpy", card.id)));
+    }
 }
 
-#[derive(Resource, Deref, DerefMut)]
+/// Ending visual novel of each endgame scenario by id, `endgame/{id}.rpy`
+#[derive(Resource, Default, Deref, DerefMut)]
 pub struct EndGameScenarioHandle(HashMap<String, Handle<Rpy>>);
 
-fn load_endgame_scenario(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut scenario_handles = HashMap::new();
-
-    scenario_handles.insert(
-        "balanced_recovery".to_string(),
-        asset_server.load("endgame/balanced_recovery.rpy"),
-    );
-    scenario_handles.insert(
-        "carrer_success".to_string(),
-        asset_server.load("endgame/carrer_success.rpy"),
-    );
-    scenario_handles.insert(
-        "creative_fullfilment".to_string(),
-        asset_server.load("endgame/creative_fullfilment.rpy"),
-    );
-    scenario_handles.insert(
-        "hallucination_reality_break".to_string(),
-        asset_server.load("endgame/hallucination_reality_break.rpy"),
-    );
-    scenario_handles.insert(
-        "health_crisis_death".to_string(),
-        asset_server.load("endgame/health_crisis_death.rpy"),
-    );
-    scenario_handles.insert(
-        "monotonous_survival".to_string(),
-        asset_server.load("endgame/monotonous_survival.rpy"),
-    );
-    scenario_handles.insert(
-        "psychiatric_hospitalization".to_string(),
-        asset_server.load("endgame/psychiatric_hospitalization.rpy"),
-    );
-    scenario_handles.insert(
-        "romantic_recovery".to_string(),
-        asset_server.load("endgame/romantic_recovery.rpy"),
-    );
-    scenario_handles.insert(
-        "social_reconnection".to_string(),
-        asset_server.load("endgame/social_reconnection.rpy"),
-    );
-    scenario_handles.insert(
-        "suicide_attempt".to_string(),
-        asset_server.load("endgame/suicide_attempt.rpy"),
-    );
+fn load_endgame_scenario(
+    mut scenario_handle: ResMut<EndGameScenarioHandle>,
+    asset_server: Res<AssetServer>,
+    end_game_scenarios_handle: Option<Res<EndGameScenariosHandle>>,
+    end_game_scenarios_assets: Res<Assets<EndGameScenarios>>,
+    mut asset_events: EventReader<AssetEvent<EndGameScenarios>>,
+) {
+    let Some(end_game_scenarios_handle) = end_game_scenarios_handle else {
+        return;
+    };
+    if !asset_events.read().any(|event| {
+        event.is_loaded_with_dependencies(end_game_scenarios_handle.id())
+            || event.is_modified(end_game_scenarios_handle.id())
+    }) {
+        return;
+    }
+    let Some(scenarios) = end_game_scenarios_assets.get(end_game_scenarios_handle.id()) else {
+        return;
+    };
 
-    let scenario_handle = EndGameScenarioHandle(scenario_handles);
-    commands.insert_resource(scenario_handle);
+    scenario_handle.retain(|id, _| scenarios.iter().any(|scenario| scenario.id == *id));
+    for scenario in scenarios.iter() {
+        scenario_handle
+            .entry(scenario.id.clone())
+            .or_insert_with(|| asset_server.load(format!("endgame/{}.rpy", scenario.id)));
+    }
 }
 
 pub fn start_visual_novel(
@@ -145,18 +112,3 @@ pub fn handle_novel_end(
         ew_cutscene_end.write(CutsceneEndEvent {});
     }
 }
-
-pub fn handle_game_over(
-    mut er_game_over: EventReader<EndGameEvent>,
-    mut novel_events: EventWriter<EventStartScenario>,
-    scenario: Res<EndGameScenarioHandle>,
-    rpy_assets: Res<Assets<Rpy>>,
-) {
-    for e in er_game_over.read() {
-        if let Some(scenario_handle) = scenario.get(&e.scenario_id) {
-            if let Some(rpy) = rpy_assets.get(scenario_handle.id()) {
-                novel_events.write(EventStartScenario { ast: rpy.0.clone() });
-            }
-        }
-    }
-}
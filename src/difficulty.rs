@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use bevy_common_assets::json::JsonAssetPlugin;
+use serde::Deserialize;
+
+use crate::AppState;
+use crate::logic::{Difficulty, Game, GameRules};
+use crate::save::apply_loaded_game;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(JsonAssetPlugin::<DifficultyPresets>::new(&["json"]))
+            .init_resource::<GameDifficulty>()
+            .add_systems(Startup, load_difficulty_presets)
+            .add_systems(
+                OnEnter(AppState::Game),
+                start_new_game.before(apply_loaded_game),
+            );
+    }
+}
+
+#[derive(Deserialize, Asset, TypePath, Deref, DerefMut)]
+pub struct DifficultyPresets(pub Vec<Difficulty>);
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct DifficultyPresetsHandle(pub Handle<DifficultyPresets>);
+
+/// Difficulty picked in the main menu, new games are set up with it
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct GameDifficulty(pub Difficulty);
+
+fn load_difficulty_presets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DifficultyPresetsHandle(
+        asset_server.load("difficulty.json"),
+    ));
+}
+
+// A loaded save replaces these rules right after, difficulty included
+fn start_new_game(difficulty: Res<GameDifficulty>, mut rules: ResMut<GameRules>) {
+    info!("Starting a new game on {}", difficulty.name);
+    rules.0 = Game::with_difficulty(difficulty.0.clone());
+}
@@ -4,6 +4,8 @@ use std::fmt::Debug;
 use crate::navigation::{GridPos, NavigateToTile, NavigationGrid, TileSize};
 use crate::player::PlayerMarker;
 
+use game_rules::{Furniture, navigable_object};
+
 pub struct GameObjectsPlugin;
 
 // Events
@@ -255,103 +257,47 @@ pub fn handle_object_navigation_events(
         let player_grid_pos =
             navigation_grid.world_to_grid(player_transform.translation, tile_size.0);
 
-        if let Some(object_position) = match event.object_name.as_ref() {
-            "bed" => {
-                let beds = q_objects.p0();
-                let transforms: Vec<Vec3> = beds
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "kitchen" => {
-                let kitchens = q_objects.p1();
-                let transforms: Vec<Vec3> = kitchens
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "computer" => {
-                let computerdesks = q_objects.p2();
-                let transforms: Vec<Vec3> = computerdesks
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "fridge" => {
-                let kitchens = q_objects.p1();
-                let transforms: Vec<Vec3> = kitchens
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "phone" => {
-                let phones = q_objects.p5();
-                let transforms: Vec<Vec3> = phones
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "bathroom" => {
-                let sinks = q_objects.p3();
-                let transforms: Vec<Vec3> = sinks
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "tv" => {
-                let gameconsoles = q_objects.p4();
-                let transforms: Vec<Vec3> = gameconsoles
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            "gameconsole" => {
-                let gameconsoles = q_objects.p4();
-                let transforms: Vec<Vec3> = gameconsoles
-                    .iter()
-                    .map(|(_, transform, _)| transform.translation)
-                    .collect();
-                let mean_translation = transforms.iter().sum::<Vec3>() / transforms.len() as f32;
-                Some(mean_translation)
-            }
-            _ => None,
-        } {
-            if let Some(target_grid_pos) = find_nearest_walkable_tile(
-                object_position.truncate(),
-                &walkable_tiles_query,
-                &navigation_grid,
-                &tile_size,
-            ) {
-                tile_nav_events.write(NavigateToTile {
-                    from: player_grid_pos,
-                    to: target_grid_pos,
-                });
-
-                info!(
-                    "Moving from {:?} to nearest walkable tile {:?} for object: {}",
-                    player_grid_pos, target_grid_pos, event.object_name
-                );
-            } else {
-                warn!("No walkable tiles found near object: {}", event.object_name);
-            }
+        let Some(furniture) = navigable_object(&event.object_name) else {
+            continue;
+        };
+        let object_position = match furniture {
+            Furniture::Bed => mean_translation(&q_objects.p0()),
+            Furniture::Kitchen => mean_translation(&q_objects.p1()),
+            Furniture::ComputerDesk => mean_translation(&q_objects.p2()),
+            Furniture::Sink => mean_translation(&q_objects.p3()),
+            Furniture::GameConsole => mean_translation(&q_objects.p4()),
+            Furniture::Phone => mean_translation(&q_objects.p5()),
+        };
+
+        if let Some(target_grid_pos) = find_nearest_walkable_tile(
+            object_position.truncate(),
+            &walkable_tiles_query,
+            &navigation_grid,
+            &tile_size,
+        ) {
+            tile_nav_events.write(NavigateToTile {
+                from: player_grid_pos,
+                to: target_grid_pos,
+            });
+
+            info!(
+                "Moving from {:?} to nearest walkable tile {:?} for object: {}",
+                player_grid_pos, target_grid_pos, event.object_name
+            );
+        } else {
+            warn!("No walkable tiles found near object: {}", event.object_name);
         }
     }
 }
 
+fn mean_translation<T: Component>(objects: &Query<(Entity, &Transform, &T)>) -> Vec3 {
+    let transforms: Vec<Vec3> = objects
+        .iter()
+        .map(|(_, transform, _)| transform.translation)
+        .collect();
+    transforms.iter().sum::<Vec3>() / transforms.len() as f32
+}
+
 fn find_nearest_walkable_tile(
     target_position: Vec2,
     walkable_tiles_query: &Query<&Transform, (With<WalkableTile>, Without<PlayerMarker>)>,
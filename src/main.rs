@@ -19,11 +19,16 @@ use crate::{
     },
     cutscene::CutscenePlugin,
     cutscene_menu::GameMenuPlugin,
+    difficulty::DifficultyPlugin,
     endgame::{EndGamePlugin, EndGameScenarios, EndGameScenariosHandle},
     logic::{CutsceneEndEvent, CutsceneStartEvent, GameLogicPlugin},
     main_menu::MainMenuPlugin,
     player::PlayerPlugin,
+    replay::ReplayPlugin,
+    save::SavePlugin,
+    shop::ShopPlugin,
     splashscreen::SplashscreenPlugin,
+    status_effects::StatusEffectsPlugin,
     thoughts::CharacterThoughtsPlugin,
     ui::GameUIPlugin,
 };
@@ -31,14 +36,19 @@ use crate::{
 mod cards;
 mod cutscene;
 mod cutscene_menu;
+mod difficulty;
 mod endgame;
 mod game_objects;
 mod logic;
 mod main_menu;
 mod navigation;
 mod player;
+mod replay;
+mod save;
+mod shop;
 mod splashscreen;
 mod sprites;
+mod status_effects;
 mod thoughts;
 mod ui;
 
@@ -48,6 +58,7 @@ enum AppState {
     Splashscreen,
     MainMenu,
     Game,
+    GameOver,
 }
 
 fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -199,6 +210,13 @@ fn main() {
             EndGamePlugin,
             SplashscreenPlugin,
         ))
+        .add_plugins((
+            SavePlugin,
+            ReplayPlugin,
+            DifficultyPlugin,
+            StatusEffectsPlugin,
+            ShopPlugin,
+        ))
         .add_systems(Startup, startup)
         .add_systems(OnEnter(AppState::Game), startup_game)
         .add_systems(
@@ -8,6 +8,8 @@ use bevy_hui::prelude::*;
 use bevy_kira_audio::*;
 
 use crate::AppState;
+use crate::difficulty::{DifficultyPresets, DifficultyPresetsHandle, GameDifficulty};
+use crate::save::{LoadedGame, MANUAL_SLOTS, SaveGame, SaveSlot};
 
 pub struct MainMenuPlugin;
 
@@ -25,6 +27,10 @@ impl Plugin for MainMenuPlugin {
 #[derive(Component)]
 pub struct MainMenuResource {}
 
+/// The screen currently shown on top of the table, swapped when navigating the menu
+#[derive(Component)]
+struct MenuScreen;
+
 #[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
 pub struct ShaderMaterial {}
 
@@ -56,11 +62,7 @@ pub fn show_menu(
     ));
 
     // menu
-    commands.spawn((
-        HtmlNode(asset_server.load("menu/main_menu.html")),
-        TemplateProperties::default(),
-        MainMenuResource {},
-    ));
+    spawn_main_screen(&mut commands, &asset_server);
 
     audio
         .play(asset_server.load("music/echo_code.mp3"))
@@ -71,18 +73,128 @@ pub fn show_menu(
         ));
 
     // main menu handler
+    html_funcs.register("start_game", show_difficulty_select);
+
     html_funcs.register(
-        "start_game",
-        |In(_), mut app_state: ResMut<NextState<AppState>>| {
-            app_state.set(AppState::Game);
+        "back_to_menu",
+        |In(_),
+         mut commands: Commands,
+         asset_server: Res<AssetServer>,
+         q_screens: Query<Entity, With<MenuScreen>>| {
+            for entity in q_screens.iter() {
+                commands.entity(entity).despawn();
+            }
+            spawn_main_screen(&mut commands, &asset_server);
         },
     );
 
+    html_funcs.register(
+        "continue_game",
+        |In(_), mut commands: Commands, mut app_state: ResMut<NextState<AppState>>| {
+            let Some(slot) = SaveGame::latest() else {
+                warn!("No saved game to continue");
+                return;
+            };
+            load_game(slot, &mut commands, &mut app_state);
+        },
+    );
+
+    for (slot, _) in MANUAL_SLOTS {
+        html_funcs.register(
+            format!("load_slot_{slot}"),
+            move |In(_), mut commands: Commands, mut app_state: ResMut<NextState<AppState>>| {
+                load_game(SaveSlot::Manual(slot), &mut commands, &mut app_state);
+            },
+        );
+    }
+
     for (_, mut camera, _) in q_cameras.iter_mut() {
         camera.order = 2;
     }
 }
 
+fn spawn_main_screen(commands: &mut Commands, asset_server: &AssetServer) {
+    commands.spawn((
+        HtmlNode(asset_server.load("menu/main_menu.html")),
+        TemplateProperties::default(),
+        MainMenuResource {},
+        MenuScreen,
+    ));
+}
+
+fn show_difficulty_select(
+    In(_): In<Entity>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut html_funcs: HtmlFunctions,
+    presets_handle: Option<Res<DifficultyPresetsHandle>>,
+    presets_assets: Res<Assets<DifficultyPresets>>,
+    q_screens: Query<Entity, With<MenuScreen>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    let Some(presets) = presets_handle.and_then(|handle| presets_assets.get(handle.id())) else {
+        warn!("Difficulty presets not loaded, starting on the default difficulty");
+        app_state.set(AppState::Game);
+        return;
+    };
+
+    for entity in q_screens.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            MainMenuResource {},
+            MenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                HtmlNode(asset_server.load("menu/difficulty_menu.html")),
+                TemplateProperties::default(),
+            ));
+
+            for difficulty in presets.iter() {
+                let action = format!("select_difficulty_{}", difficulty.id);
+                parent.spawn((
+                    HtmlNode(asset_server.load("menu/difficulty_option.html")),
+                    TemplateProperties::default()
+                        .with("name", &difficulty.name.to_uppercase())
+                        .with("description", &difficulty.description)
+                        .with("action", &action),
+                ));
+
+                let difficulty = difficulty.clone();
+                html_funcs.register(
+                    action,
+                    move |In(_),
+                          mut commands: Commands,
+                          mut app_state: ResMut<NextState<AppState>>| {
+                        commands.insert_resource(GameDifficulty(difficulty.clone()));
+                        app_state.set(AppState::Game);
+                    },
+                );
+            }
+        });
+}
+
+fn load_game(slot: SaveSlot, commands: &mut Commands, app_state: &mut NextState<AppState>) {
+    match SaveGame::read(slot) {
+        Ok(save) => {
+            commands.insert_resource(LoadedGame(save));
+            app_state.set(AppState::Game);
+        }
+        Err(err) => error!("Failed to load {slot:?}: {err:#}"),
+    }
+}
+
 pub fn despawn_menu(
     mut commands: Commands,
     q_main_menu_entities: Query<(Entity, &MainMenuResource)>,
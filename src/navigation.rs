@@ -4,6 +4,7 @@ use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use crate::AppState;
+use crate::logic::GameRng;
 use crate::{game_objects::WalkableTile, player::PlayerMarker};
 
 const TILE_SIZE: f32 = 32.0;
@@ -86,14 +87,15 @@ impl NavigationGrid {
         }
     }
 
-    pub fn get_random_walkable_tile(&self) -> Option<GridPos> {
+    pub fn get_random_walkable_tile(&self, rng: &mut impl Rng) -> Option<GridPos> {
         if self.walkable.is_empty() {
             return None;
         }
 
-        let tiles: Vec<&(i32, i32)> = self.walkable.iter().collect();
-        let mut rng = rand::thread_rng();
-        let random_index = rng.gen_range(0..tiles.len());
+        // Sorted, since set order differs between runs and would defeat the seed
+        let mut tiles: Vec<&(i32, i32)> = self.walkable.iter().collect();
+        tiles.sort();
+        let random_index = rng.random_range(0..tiles.len());
 
         if let Some(&(x, y)) = tiles.get(random_index) {
             Some(GridPos { x: *x, y: *y })
@@ -103,12 +105,16 @@ impl NavigationGrid {
     }
 
     /// Get a random walkable tile that's different from the current position
-    pub fn get_random_walkable_tile_excluding(&self, exclude: GridPos) -> Option<GridPos> {
+    pub fn get_random_walkable_tile_excluding(
+        &self,
+        exclude: GridPos,
+        rng: &mut impl Rng,
+    ) -> Option<GridPos> {
         if self.walkable.len() <= 1 {
             return None;
         }
 
-        let tiles: Vec<&(i32, i32)> = self
+        let mut tiles: Vec<&(i32, i32)> = self
             .walkable
             .iter()
             .filter(|&&(x, y)| GridPos { x, y } != exclude)
@@ -118,8 +124,8 @@ impl NavigationGrid {
             return None;
         }
 
-        let mut rng = rand::thread_rng();
-        let random_index = rng.gen_range(0..tiles.len());
+        tiles.sort();
+        let random_index = rng.random_range(0..tiles.len());
 
         if let Some(&(x, y)) = tiles.get(random_index) {
             Some(GridPos { x: *x, y: *y })
@@ -243,6 +249,7 @@ fn handle_go_to_random_tile(
     player_query: Query<&Transform, With<PlayerMarker>>,
     navigation_grid: Res<NavigationGrid>,
     tile_size: Res<TileSize>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     for _event in random_events.read() {
         let Ok(player_transform) = player_query.single() else {
@@ -254,7 +261,7 @@ fn handle_go_to_random_tile(
 
         // Get a random walkable tile that's different from current position
         if let Some(random_tile) =
-            navigation_grid.get_random_walkable_tile_excluding(player_grid_pos)
+            navigation_grid.get_random_walkable_tile_excluding(player_grid_pos, &mut **game_rng)
         {
             nav_events.write(NavigateToTile {
                 from: player_grid_pos,
@@ -270,6 +277,7 @@ fn handle_go_to_tile(
     player_query: Query<&Transform, With<PlayerMarker>>,
     navigation_grid: Res<NavigationGrid>,
     tile_size: Res<TileSize>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     for _event in random_events.read() {
         let Ok(player_transform) = player_query.single() else {
@@ -281,7 +289,7 @@ fn handle_go_to_tile(
 
         // Get a random walkable tile that's different from current position
         if let Some(random_tile) =
-            navigation_grid.get_random_walkable_tile_excluding(player_grid_pos)
+            navigation_grid.get_random_walkable_tile_excluding(player_grid_pos, &mut **game_rng)
         {
             nav_events.write(NavigateToTile {
                 from: player_grid_pos,
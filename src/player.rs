@@ -1,6 +1,6 @@
 use crate::AppState;
 use crate::cards::{ActivityCard, GameCard};
-use crate::logic::{ActionCompletedEvent, CutsceneEndEvent, CutsceneStartEvent, GamePhaseState};
+use crate::logic::{ActionCompletedEvent, CutsceneEndEvent, CutsceneStartEvent, GameRules};
 use crate::navigation::{GridPos, MovePlayerCommand, TileSize};
 use crate::sprites::{
     AnimatedCharacterSprite, AnimatedCharacterType, AnimationDirection, AnimationState,
@@ -220,11 +220,11 @@ fn handle_cutscene_end(
 pub fn handle_player_destination_reached(
     mut destination_events: EventReader<PlayerDestinationReachedEvent>,
     mut ew_action_completed: EventWriter<ActionCompletedEvent>,
-    phase_state: Res<GamePhaseState>,
+    rules: Res<GameRules>,
     q_cards: Query<(Entity, &Card<GameCard>)>,
 ) {
     for _ in destination_events.read() {
-        if let Some(selected_card_id) = phase_state.selected_card_id
+        if let Some(selected_card_id) = rules.phase.selected_card_id
             && let Some(card) = q_cards
                 .iter()
                 .find(|(_, card)| card.data.id() == selected_card_id)
@@ -0,0 +1,294 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use bevy::prelude::*;
+use bevy_la_mesa::{Card, Hand};
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+use crate::cards::GameCard;
+use crate::difficulty::GameDifficulty;
+use crate::logic::{
+    CardSelectedEvent, CutsceneEndEvent, Difficulty, GameAction, GameActionEvent, GamePhase,
+    GameRng, GameRules, MulliganEvent, PassTurnEvent, TurnOverEvent, handle_game_actions,
+    queue_game_actions,
+};
+use crate::save::{LoadedGame, apply_loaded_game};
+
+const REPLAY_DIR: &str = "replays";
+
+/// Pause between replayed decisions, so draws and animations can settle
+const REPLAY_STEP_SECONDS: f32 = 1.0;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        // GAME_REPLAY=<file> re-drives a recorded run instead of recording a new one
+        if let Ok(path) = std::env::var("GAME_REPLAY") {
+            match Replay::read(Path::new(&path)) {
+                Ok(replay) => {
+                    info!(
+                        "Playing back {path}: {} decisions, seed {}",
+                        replay.decisions.len(),
+                        replay.seed
+                    );
+                    app.insert_resource(GameRng::from_seed(replay.seed))
+                        .insert_resource(GameDifficulty(replay.difficulty.clone()))
+                        .insert_resource(ReplayPlayback::new(replay));
+                }
+                Err(err) => error!("Failed to load replay {path}: {err:#}"),
+            }
+        }
+
+        app.add_systems(OnEnter(AppState::MainMenu), skip_menu_in_playback)
+            .add_systems(
+                OnEnter(AppState::Game),
+                start_recording.before(apply_loaded_game),
+            )
+            .add_systems(
+                Update,
+                (
+                    record_decisions
+                        .after(queue_game_actions)
+                        .before(handle_game_actions),
+                    play_back_decisions,
+                )
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
+
+/// A single player decision, everything else follows from the seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayInput {
+    SelectCard(u32),
+    Mulligan(Vec<u32>),
+    PassTurn,
+    EndTurn,
+    AdvanceCutscene,
+    BuyCard(u32),
+    RemoveCard(u32),
+    FinishDeckBuilding,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayDecision {
+    pub turn: u32,
+    pub input: ReplayInput,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    pub decisions: Vec<ReplayDecision>,
+}
+
+impl Replay {
+    fn path(seed: u64) -> PathBuf {
+        PathBuf::from(REPLAY_DIR).join(format!("run_{seed}.json"))
+    }
+
+    pub fn write(&self) -> anyhow::Result<()> {
+        let path = Self::path(self.seed);
+        fs::create_dir_all(REPLAY_DIR).context("failed to create replay directory")?;
+        let json = serde_json::to_string(self)?;
+        fs::write(&path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("failed to parse {}", path.display()))
+    }
+}
+
+/// Run being recorded, only present for runs started from a fresh game
+#[derive(Resource, Deref, DerefMut)]
+pub struct ReplayRecorder(Replay);
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    next: usize,
+    step_timer: Timer,
+}
+
+impl ReplayPlayback {
+    fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next: 0,
+            step_timer: Timer::from_seconds(REPLAY_STEP_SECONDS, TimerMode::Once),
+        }
+    }
+}
+
+fn skip_menu_in_playback(
+    playback: Option<Res<ReplayPlayback>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if playback.is_some() {
+        app_state.set(AppState::Game);
+    }
+}
+
+fn start_recording(
+    mut commands: Commands,
+    game_rng: Res<GameRng>,
+    difficulty: Res<GameDifficulty>,
+    loaded_game: Option<Res<LoadedGame>>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    // A loaded save or a replay in progress can't be reproduced from the seed alone
+    if loaded_game.is_some() || playback.is_some() {
+        commands.remove_resource::<ReplayRecorder>();
+        return;
+    }
+
+    commands.insert_resource(ReplayRecorder(Replay {
+        seed: game_rng.seed,
+        difficulty: difficulty.0.clone(),
+        decisions: Vec::new(),
+    }));
+}
+
+fn record_decisions(
+    mut game_actions: EventReader<GameActionEvent>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+    rules: Res<GameRules>,
+) {
+    let Some(mut recorder) = recorder else {
+        game_actions.clear();
+        return;
+    };
+
+    let mut recorded = false;
+    for GameActionEvent(action) in game_actions.read() {
+        let input = match action {
+            GameAction::PlayCard { card, .. } => ReplayInput::SelectCard(card.id),
+            GameAction::Mulligan { card_ids, .. } => ReplayInput::Mulligan(card_ids.clone()),
+            GameAction::PassTurn => ReplayInput::PassTurn,
+            GameAction::EndTurn => ReplayInput::EndTurn,
+            GameAction::EndCutscene if rules.phase.cutscene_active => ReplayInput::AdvanceCutscene,
+            GameAction::BuyCard { card_id } => ReplayInput::BuyCard(*card_id),
+            GameAction::RemoveCard { card_id } => ReplayInput::RemoveCard(*card_id),
+            GameAction::FinishDeckBuilding => ReplayInput::FinishDeckBuilding,
+            _ => continue,
+        };
+
+        recorder.decisions.push(ReplayDecision {
+            turn: rules.phase.turn_number,
+            input,
+        });
+        recorded = true;
+    }
+
+    // Flushed on every decision, so a crash still leaves a usable replay
+    if recorded && let Err(err) = recorder.write() {
+        error!("Failed to write replay: {err:#}");
+    }
+}
+
+fn play_back_decisions(
+    mut commands: Commands,
+    time: Res<Time>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    rules: Res<GameRules>,
+    q_cards: Query<(&Card<GameCard>, &Hand)>,
+    mut ew_card_selected: EventWriter<CardSelectedEvent>,
+    mut ew_mulligan: EventWriter<MulliganEvent>,
+    mut ew_pass_turn: EventWriter<PassTurnEvent>,
+    mut ew_game_actions: EventWriter<GameActionEvent>,
+    mut ew_turn_over: EventWriter<TurnOverEvent>,
+    mut ew_cutscene_end: EventWriter<CutsceneEndEvent>,
+) {
+    let Some(mut playback) = playback else {
+        return;
+    };
+
+    if !playback.step_timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let Some(decision) = playback.replay.decisions.get(playback.next).cloned() else {
+        info!("Replay finished");
+        commands.remove_resource::<ReplayPlayback>();
+        return;
+    };
+
+    let phase_state = &rules.phase;
+    let shop_open = rules.state.shop_offer.is_some();
+    // Wait until the game asks for the kind of decision that was recorded
+    let applied = match decision.input {
+        ReplayInput::SelectCard(card_id)
+            if phase_state.current_phase == GamePhase::CardSelection
+                && !phase_state.cutscene_active =>
+        {
+            let card = q_cards
+                .iter()
+                .filter(|(_, hand)| hand.player == 1)
+                .find_map(|(card, _)| {
+                    card.data
+                        .to_activity_card()
+                        .filter(|activity_card| activity_card.id == card_id)
+                });
+            if let Some(card) = card {
+                ew_card_selected.write(CardSelectedEvent(card.clone()));
+            }
+            card.is_some()
+        }
+        ReplayInput::Mulligan(card_ids)
+            if phase_state.current_phase == GamePhase::CardSelection
+                && !phase_state.cutscene_active =>
+        {
+            ew_mulligan.write(MulliganEvent(card_ids));
+            true
+        }
+        ReplayInput::PassTurn
+            if phase_state.current_phase == GamePhase::CardSelection
+                && !phase_state.cutscene_active =>
+        {
+            ew_pass_turn.write(PassTurnEvent);
+            true
+        }
+        ReplayInput::EndTurn
+            if phase_state.current_phase == GamePhase::TurnOver && !phase_state.cutscene_active =>
+        {
+            ew_turn_over.write(TurnOverEvent);
+            true
+        }
+        ReplayInput::AdvanceCutscene if phase_state.cutscene_active => {
+            ew_cutscene_end.write(CutsceneEndEvent);
+            true
+        }
+        // The shop answers only once its offer is stocked
+        ReplayInput::BuyCard(card_id) if shop_open => {
+            ew_game_actions.write(GameActionEvent(GameAction::BuyCard { card_id }));
+            true
+        }
+        ReplayInput::RemoveCard(card_id) if shop_open => {
+            ew_game_actions.write(GameActionEvent(GameAction::RemoveCard { card_id }));
+            true
+        }
+        ReplayInput::FinishDeckBuilding if shop_open => {
+            ew_game_actions.write(GameActionEvent(GameAction::FinishDeckBuilding));
+            true
+        }
+        _ => false,
+    };
+
+    if applied {
+        if decision.turn != phase_state.turn_number {
+            warn!(
+                "Replay out of sync: decision {} was recorded on turn {}, applied on turn {}",
+                playback.next, decision.turn, phase_state.turn_number
+            );
+        }
+        playback.next += 1;
+        playback.step_timer.reset();
+    }
+}
@@ -0,0 +1,392 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use bevy::prelude::*;
+use bevy_defer::{AsyncCommandsExtension, AsyncWorld};
+use bevy_la_mesa::events::{DrawToHand, PlaceCardOnTable, RenderDeck};
+use bevy_la_mesa::{Card, CardOnTable, DeckArea, Hand};
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+use crate::cards::{CardVariant, GameCard};
+use crate::logic::{
+    CutsceneEndEvent, CutsceneStartEvent, CutsceneTrigger, Game, GamePhase, GameRules,
+    PhaseChangedEvent, TurnOverEvent, handle_game_actions,
+};
+use crate::player::PlayerMarker;
+use crate::thoughts::ActionLog;
+
+const SAVE_DIR: &str = "saves";
+
+/// Manual slots, saved with F5, F6 and F7
+pub const MANUAL_SLOTS: [(u32, KeyCode); 3] =
+    [(1, KeyCode::F5), (2, KeyCode::F6), (3, KeyCode::F7)];
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveCutscene>()
+            .add_event::<SaveGameEvent>()
+            .add_systems(OnEnter(AppState::Game), apply_loaded_game)
+            .add_systems(OnEnter(AppState::GameOver), discard_autosave)
+            .add_systems(
+                Update,
+                (
+                    track_active_cutscene,
+                    autosave_on_turn_over.after(handle_game_actions),
+                    handle_save_keys,
+                    save_game
+                        .after(autosave_on_turn_over)
+                        .after(handle_save_keys),
+                    restore_player_position,
+                    restore_hands,
+                    restore_table_cards,
+                )
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSlot {
+    Autosave,
+    Manual(u32),
+}
+
+impl SaveSlot {
+    fn path(&self) -> PathBuf {
+        match self {
+            SaveSlot::Autosave => PathBuf::from(SAVE_DIR).join("autosave.json"),
+            SaveSlot::Manual(slot) => PathBuf::from(SAVE_DIR).join(format!("slot_{slot}.json")),
+        }
+    }
+
+    fn all() -> impl Iterator<Item = SaveSlot> {
+        std::iter::once(SaveSlot::Autosave)
+            .chain(MANUAL_SLOTS.iter().map(|(slot, _)| SaveSlot::Manual(*slot)))
+    }
+}
+
+#[derive(Event)]
+pub struct SaveGameEvent {
+    pub slot: SaveSlot,
+}
+
+/// Everything needed to resume a run
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub rules: Game,
+    pub action_log: ActionLog,
+    pub hands: Vec<SavedCard>,
+    pub player_position: Option<(f32, f32)>,
+    pub active_cutscene: Option<SavedCutscene>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedCard {
+    pub card: GameCard,
+    pub on_table: bool,
+}
+
+impl SavedCard {
+    // Activity cards belong to the character, schizophrenic cards to the adversary
+    fn player(&self) -> usize {
+        match self.card.card_variant {
+            CardVariant::Activity(_) => 1,
+            CardVariant::Schizophrenic(_) => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedCutscene {
+    pub cutscene_id: String,
+    pub card_id: Option<u32>,
+    pub trigger_reason: CutsceneTrigger,
+}
+
+impl SaveGame {
+    pub fn write(&self, slot: SaveSlot) -> anyhow::Result<()> {
+        let path = slot.path();
+        fs::create_dir_all(SAVE_DIR).context("failed to create save directory")?;
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn read(slot: SaveSlot) -> anyhow::Result<Self> {
+        let path = slot.path();
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Most recently written save across all slots
+    pub fn latest() -> Option<SaveSlot> {
+        SaveSlot::all()
+            .filter_map(|slot| {
+                let modified = fs::metadata(slot.path()).ok()?.modified().ok()?;
+                Some((slot, modified))
+            })
+            .max_by_key(|(_, modified)| *modified)
+            .map(|(slot, _)| slot)
+    }
+}
+
+/// Save picked in the main menu, applied when the game state is entered
+#[derive(Resource)]
+pub struct LoadedGame(pub SaveGame);
+
+/// Cutscene currently on screen, the rules only keep its id
+#[derive(Resource, Default)]
+pub struct ActiveCutscene(pub Option<SavedCutscene>);
+
+#[derive(Resource)]
+struct PendingPlayerPosition(Vec2);
+
+#[derive(Resource)]
+struct PendingHands(Vec<SavedCard>);
+
+#[derive(Resource)]
+struct PendingTableCards(Vec<(u32, usize)>);
+
+fn track_active_cutscene(
+    mut cutscene_start_events: EventReader<CutsceneStartEvent>,
+    mut cutscene_end_events: EventReader<CutsceneEndEvent>,
+    mut active_cutscene: ResMut<ActiveCutscene>,
+) {
+    for event in cutscene_start_events.read() {
+        active_cutscene.0 = Some(SavedCutscene {
+            cutscene_id: event.cutscene_id.clone(),
+            card_id: event.card_id,
+            trigger_reason: event.trigger_reason.clone(),
+        });
+    }
+
+    for _ in cutscene_end_events.read() {
+        active_cutscene.0 = None;
+    }
+}
+
+fn autosave_on_turn_over(
+    mut turn_over_events: EventReader<TurnOverEvent>,
+    rules: Res<GameRules>,
+    mut save_events: EventWriter<SaveGameEvent>,
+) {
+    for _ in turn_over_events.read() {
+        // A finished run is nothing to continue from
+        if rules.phase.game_over.is_some() {
+            continue;
+        }
+        save_events.write(SaveGameEvent {
+            slot: SaveSlot::Autosave,
+        });
+    }
+}
+
+/// Endings decided by the scenarios come after the last autosave, so CONTINUE would land
+/// right back in the ending
+fn discard_autosave() {
+    let path = SaveSlot::Autosave.path();
+    if let Err(err) = fs::remove_file(&path)
+        && err.kind() != std::io::ErrorKind::NotFound
+    {
+        error!("Failed to remove {}: {err}", path.display());
+    }
+}
+
+fn handle_save_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut save_events: EventWriter<SaveGameEvent>,
+) {
+    for (slot, key) in MANUAL_SLOTS {
+        if keyboard.just_pressed(key) {
+            save_events.write(SaveGameEvent {
+                slot: SaveSlot::Manual(slot),
+            });
+        }
+    }
+}
+
+fn save_game(
+    mut save_events: EventReader<SaveGameEvent>,
+    rules: Res<GameRules>,
+    action_log: Res<ActionLog>,
+    active_cutscene: Res<ActiveCutscene>,
+    q_cards: Query<(&Card<GameCard>, Has<CardOnTable>)>,
+    q_player: Query<&Transform, With<PlayerMarker>>,
+) {
+    for event in save_events.read() {
+        // The player's hand is kept from turn to turn, so cards are saved in every phase
+        let hands = q_cards
+            .iter()
+            .map(|(card, on_table)| SavedCard {
+                card: card.data.clone(),
+                on_table,
+            })
+            .collect();
+
+        let save = SaveGame {
+            rules: rules.0.clone(),
+            action_log: action_log.clone(),
+            hands,
+            player_position: q_player
+                .single()
+                .ok()
+                .map(|transform| (transform.translation.x, transform.translation.y)),
+            active_cutscene: active_cutscene.0.clone(),
+        };
+
+        match save.write(event.slot) {
+            Ok(()) => info!("Saved game to {:?}", event.slot),
+            Err(err) => error!("Failed to save game to {:?}: {err:#}", event.slot),
+        }
+    }
+}
+
+pub fn apply_loaded_game(
+    mut commands: Commands,
+    loaded_game: Option<Res<LoadedGame>>,
+    mut rules: ResMut<GameRules>,
+    mut action_log: ResMut<ActionLog>,
+    mut active_cutscene: ResMut<ActiveCutscene>,
+) {
+    let Some(loaded_game) = loaded_game else {
+        return;
+    };
+    let save = &loaded_game.0;
+
+    rules.0 = save.rules.clone();
+    *action_log = save.action_log.clone();
+    active_cutscene.0 = save.active_cutscene.clone();
+
+    if let Some((x, y)) = save.player_position {
+        commands.insert_resource(PendingPlayerPosition(Vec2::new(x, y)));
+    }
+    if !save.hands.is_empty() {
+        commands.insert_resource(PendingHands(save.hands.clone()));
+    }
+
+    // Once the room and the hands are back, resume whatever was running when the game was saved
+    let cutscene = save.active_cutscene.clone();
+    let phase = save.rules.phase.current_phase;
+    commands.spawn_task(move || async move {
+        AsyncWorld.sleep(3.0).await;
+        if let Some(cutscene) = cutscene {
+            AsyncWorld.send_event(CutsceneStartEvent {
+                cutscene_id: cutscene.cutscene_id,
+                card_id: cutscene.card_id,
+                trigger_reason: cutscene.trigger_reason,
+            })?;
+        } else if phase != GamePhase::CardDraw {
+            AsyncWorld.send_event(PhaseChangedEvent { new_phase: phase })?;
+        }
+        Ok(())
+    });
+
+    commands.remove_resource::<LoadedGame>();
+}
+
+fn restore_player_position(
+    mut commands: Commands,
+    pending: Option<Res<PendingPlayerPosition>>,
+    mut q_player: Query<&mut Transform, With<PlayerMarker>>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    // The player is spawned once the tiled map is ready
+    if let Ok(mut transform) = q_player.single_mut() {
+        transform.translation.x = pending.0.x;
+        transform.translation.y = pending.0.y;
+        commands.remove_resource::<PendingPlayerPosition>();
+    }
+}
+
+fn restore_hands(
+    mut commands: Commands,
+    pending: Option<Res<PendingHands>>,
+    mut ew_render_deck: EventWriter<RenderDeck<GameCard>>,
+    q_decks: Query<(Entity, &DeckArea)>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    let mut table_cards = Vec::new();
+    for (player, marker) in [(1, 1), (2, 2)] {
+        let Some((deck_entity, _)) = q_decks.iter().find(|(_, deck)| deck.marker == marker) else {
+            return;
+        };
+
+        let cards: Vec<&SavedCard> = pending
+            .0
+            .iter()
+            .filter(|saved| saved.player() == player)
+            .collect();
+        if cards.is_empty() {
+            continue;
+        }
+
+        table_cards.extend(
+            cards
+                .iter()
+                .filter(|saved| saved.on_table)
+                .map(|saved| (saved.card.id(), player)),
+        );
+
+        // A deck holding exactly the saved hand deals it back unchanged
+        let num_cards = cards.len();
+        ew_render_deck.write(RenderDeck::<GameCard> {
+            deck_entity,
+            deck: cards.into_iter().map(|saved| saved.card.clone()).collect(),
+        });
+        commands.spawn_task(move || async move {
+            AsyncWorld.sleep(1.0).await;
+            AsyncWorld.send_event(DrawToHand {
+                deck_entity,
+                num_cards,
+                player,
+            })?;
+            Ok(())
+        });
+    }
+
+    if !table_cards.is_empty() {
+        commands.insert_resource(PendingTableCards(table_cards));
+    }
+    commands.remove_resource::<PendingHands>();
+}
+
+fn restore_table_cards(
+    mut commands: Commands,
+    pending: Option<ResMut<PendingTableCards>>,
+    mut ew_place_card_on_table: EventWriter<PlaceCardOnTable>,
+    q_cards: Query<(Entity, &Card<GameCard>, &Hand)>,
+) {
+    let Some(mut pending) = pending else {
+        return;
+    };
+
+    pending.0.retain(|(card_id, player)| {
+        let Some((card_entity, _, _)) = q_cards
+            .iter()
+            .find(|(_, card, hand)| card.data.id() == *card_id && hand.player == *player)
+        else {
+            return true;
+        };
+
+        ew_place_card_on_table.write(PlaceCardOnTable {
+            card_entity,
+            marker: 1,
+            player: *player,
+        });
+        false
+    });
+
+    if pending.0.is_empty() {
+        commands.remove_resource::<PendingTableCards>();
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use bevy_hui::prelude::*;
+use rand::Rng;
+
+use crate::AppState;
+use crate::cards::{ActivityCard, ActivityCards, ActivityCardsHandle};
+use crate::logic::{
+    CardAddedToDeckEvent, CardRemovedFromDeckEvent, GameAction, GameActionEvent, GamePhase,
+    GameRng, GameRules, PhaseChangedEvent, ShopStockedEvent, handle_game_actions,
+};
+
+use game_rules::{CARD_PRICE, CARD_REMOVAL_PRICE};
+
+/// Card shop shown between days, where credits earned on stable days are spent on the deck
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (toggle_shop, render_shop)
+                .chain()
+                .after(handle_game_actions)
+                .run_if(in_state(AppState::Game)),
+        );
+    }
+}
+
+#[derive(Component)]
+struct ShopScreen;
+
+/// Stock the shop when it first opens, a shop reopened from a save keeps its offer
+fn toggle_shop(
+    mut commands: Commands,
+    mut phase_changed_events: EventReader<PhaseChangedEvent>,
+    rules: Res<GameRules>,
+    mut game_rng: ResMut<GameRng>,
+    activity_cards_handle: Option<Res<ActivityCardsHandle>>,
+    activity_cards_assets: Res<Assets<ActivityCards>>,
+    mut game_actions: EventWriter<GameActionEvent>,
+    q_screens: Query<Entity, With<ShopScreen>>,
+) {
+    for event in phase_changed_events.read() {
+        if event.new_phase != GamePhase::DeckBuilding {
+            for entity in q_screens.iter() {
+                commands.entity(entity).despawn();
+            }
+            continue;
+        }
+
+        if rules.state.shop_offer.is_some() {
+            continue;
+        }
+
+        let cards = match activity_cards_handle
+            .as_ref()
+            .and_then(|handle| activity_cards_assets.get(handle.id()))
+        {
+            Some(activity_cards) => activity_cards.to_vec(),
+            None => {
+                warn!("Activity cards not loaded, the shop has nothing on offer");
+                Vec::new()
+            }
+        };
+
+        game_actions.write(GameActionEvent(GameAction::StockShop {
+            cards,
+            shuffle_seed: game_rng.random(),
+        }));
+    }
+}
+
+/// Spawn the shop again whenever it opens or the deck changes, so prices and credits stay current
+fn render_shop(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut html_funcs: HtmlFunctions,
+    rules: Res<GameRules>,
+    mut phase_changed_events: EventReader<PhaseChangedEvent>,
+    mut shop_stocked_events: EventReader<ShopStockedEvent>,
+    mut card_added_events: EventReader<CardAddedToDeckEvent>,
+    mut card_removed_events: EventReader<CardRemovedFromDeckEvent>,
+    q_screens: Query<Entity, With<ShopScreen>>,
+) {
+    let shop_changed = phase_changed_events.read().count()
+        + shop_stocked_events.read().count()
+        + card_added_events.read().count()
+        + card_removed_events.read().count()
+        > 0;
+    let Some(offer) = rules.state.shop_offer.as_ref() else {
+        return;
+    };
+    if !shop_changed {
+        return;
+    }
+
+    for entity in q_screens.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    html_funcs.register(
+        "leave_shop",
+        |In(_), mut game_actions: EventWriter<GameActionEvent>| {
+            game_actions.write(GameActionEvent(GameAction::FinishDeckBuilding));
+        },
+    );
+
+    let mut deck: Vec<&ActivityCard> = rules.state.deck.cards().collect();
+    deck.sort_by_key(|card| card.id);
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            ShopScreen,
+            Name::new("Card Shop"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                HtmlNode(asset_server.load("menu/shop_menu.html")),
+                TemplateProperties::default().with("credits", &rules.state.credits.to_string()),
+            ));
+
+            parent
+                .spawn(Node {
+                    width: Val::Percent(80.0),
+                    padding: UiRect::top(Val::Px(80.0)),
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    align_content: AlignContent::FlexStart,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for card in offer {
+                        let action = format!("shop_buy_{}", card.id);
+                        parent.spawn((
+                            HtmlNode(asset_server.load("menu/shop_option.html")),
+                            TemplateProperties::default()
+                                .with("name", &card.name.to_uppercase())
+                                .with("description", &card.description)
+                                .with("price", &format!("BUY {CARD_PRICE}"))
+                                .with("action", &action),
+                        ));
+
+                        let card_id = card.id;
+                        html_funcs.register(
+                            action,
+                            move |In(_), mut game_actions: EventWriter<GameActionEvent>| {
+                                game_actions
+                                    .write(GameActionEvent(GameAction::BuyCard { card_id }));
+                            },
+                        );
+                    }
+
+                    for card in deck {
+                        let action = format!("shop_remove_{}", card.id);
+                        parent.spawn((
+                            HtmlNode(asset_server.load("menu/shop_option.html")),
+                            TemplateProperties::default()
+                                .with("name", &card.name.to_uppercase())
+                                .with("description", &card.description)
+                                .with("price", &format!("REMOVE {CARD_REMOVAL_PRICE}"))
+                                .with("action", &action),
+                        ));
+
+                        let card_id = card.id;
+                        html_funcs.register(
+                            action,
+                            move |In(_), mut game_actions: EventWriter<GameActionEvent>| {
+                                game_actions
+                                    .write(GameActionEvent(GameAction::RemoveCard { card_id }));
+                            },
+                        );
+                    }
+                });
+        });
+}
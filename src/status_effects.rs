@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_common_assets::json::JsonAssetPlugin;
+use serde::Deserialize;
+
+use crate::AppState;
+use crate::cards::StatusEffectDefinitions;
+use crate::logic::GameRules;
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(JsonAssetPlugin::<StatusEffects>::new(&["json"]))
+            .add_systems(Startup, load_status_effects)
+            .add_systems(
+                Update,
+                supply_status_effect_definitions.run_if(in_state(AppState::Game)),
+            );
+    }
+}
+
+#[derive(Deserialize, Asset, TypePath, Deref)]
+pub struct StatusEffects(pub StatusEffectDefinitions);
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct StatusEffectsHandle(pub Handle<StatusEffects>);
+
+fn load_status_effects(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(StatusEffectsHandle(
+        asset_server.load("status_effects.json"),
+    ));
+}
+
+// Definitions aren't saved with the game, so new and loaded games both pick them up here
+fn supply_status_effect_definitions(
+    handle: Res<StatusEffectsHandle>,
+    status_effects: Res<Assets<StatusEffects>>,
+    mut asset_events: EventReader<AssetEvent<StatusEffects>>,
+    mut rules: ResMut<GameRules>,
+) {
+    let modified = asset_events
+        .read()
+        .any(|event| event.is_modified(handle.id()));
+    if !modified && !rules.state.status_effect_definitions.is_empty() {
+        return;
+    }
+
+    if let Some(status_effects) = status_effects.get(&handle.0) {
+        rules.state.status_effect_definitions = status_effects.0.clone();
+    }
+}
@@ -2,12 +2,13 @@ use crate::{
     AppState,
     cards::{ActivityCard, Mood, ResourceType, TimeOfDay},
     logic::{
-        CardSelectedEvent, GamePhase, GamePhaseState, GameState, MoodChangedEvent,
+        CardSelectedEvent, GamePhase, GamePhaseState, GameRules, GameState, MoodChangedEvent,
         PhaseChangedEvent, ResourceChangedEvent, TimeChangedEvent,
     },
 };
 use bevy::prelude::*;
 use bevy_llm::*;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
 pub struct CharacterThoughtsPlugin;
@@ -23,6 +24,7 @@ impl Plugin for CharacterThoughtsPlugin {
                 (
                     process_thought_generation,
                     handle_llm_responses,
+                    handle_llm_rejections,
                     handle_async_llm_responses,
                     // listen_for_mood_changes,
                     listen_for_resource_crises,
@@ -35,12 +37,24 @@ impl Plugin for CharacterThoughtsPlugin {
     }
 }
 
+// Thoughts must fit the thoughts panel, the system prompt asks for the same limit
+const MAX_THOUGHT_CHARS: usize = 140;
+
+// Shown when the model output is refused by the output filters
+const FALLBACK_THOUGHT: &str = "I lose my train of thought...";
+
+// Shown next to a thought that the safety policy had to replace
+const SUPPORT_RESOURCE: &str = "If thoughts like these follow you outside the game, you don't have to carry them alone. Reach out to someone you trust or a local crisis line.";
+
 // Simplified thought generation system
 #[derive(Resource)]
 pub struct ThoughtGenerationSystem {
     pub pending_requests: std::collections::HashMap<u32, ThoughtContext>,
     pub streaming_thoughts: std::collections::HashMap<u32, String>, // Accumulate tokens
     pub next_request_id: u32,
+    pub output_filters: OutputFilterPipeline,
+    pub safety_policy: SafetyPolicy,
+    pub suppressed_streams: std::collections::HashSet<u32>, // Flagged while streaming
 }
 
 impl Default for ThoughtGenerationSystem {
@@ -49,12 +63,15 @@ impl Default for ThoughtGenerationSystem {
             pending_requests: std::collections::HashMap::new(),
             streaming_thoughts: std::collections::HashMap::new(),
             next_request_id: 1,
+            output_filters: get_thought_output_filters(),
+            safety_policy: get_thought_safety_policy(),
+            suppressed_streams: std::collections::HashSet::new(),
         }
     }
 }
 
 // Action logging system
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct ActionLog {
     pub recent_actions: VecDeque<ActionEntry>,
     pub max_history: usize,
@@ -69,7 +86,7 @@ impl Default for ActionLog {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActionEntry {
     pub action_type: ActionType,
     pub description: String,
@@ -78,7 +95,7 @@ pub struct ActionEntry {
     pub mood_at_time: Mood,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ActionType {
     CardPlayed(ActivityCard),  // Card name
     ObjectInteraction(String), // Object name
@@ -96,6 +113,16 @@ pub struct GenerateThoughtEvent {
 #[derive(Event)]
 pub struct ThoughtGeneratedEvent {
     pub text: String,
+    pub support_resource: Option<String>,
+}
+
+impl ThoughtGeneratedEvent {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            support_resource: None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -156,8 +183,7 @@ fn process_thought_generation(
     mut thought_events: EventReader<GenerateThoughtEvent>,
     mut thought_system: ResMut<ThoughtGenerationSystem>,
     mut llm_requests: EventWriter<AiGenerationRequest>,
-    game_state: Res<GameState>,
-    phase_state: Res<GamePhaseState>,
+    rules: Res<GameRules>,
     action_log: Res<ActionLog>,
 ) {
     for event in thought_events.read() {
@@ -166,7 +192,7 @@ fn process_thought_generation(
 
         let context = ThoughtContext {
             thought_type: event.thought_type.clone(),
-            current_state: GameStateSnapshot::from_game_state(&game_state, &phase_state),
+            current_state: GameStateSnapshot::from_game_state(&rules.state, &rules.phase),
             recent_actions: action_log.get_recent_actions_summary(5),
             additional_context: event.context.clone(),
         };
@@ -183,7 +209,9 @@ fn process_thought_generation(
             messages,
             Some(80),  // Max tokens for thoughts
             Some(0.8), // Temperature for varied thoughts
-        );
+        )
+        .with_output_filters(thought_system.output_filters.clone())
+        .with_safety_policy(thought_system.safety_policy.clone());
 
         thought_system.pending_requests.insert(request_id, context);
         llm_requests.write(request);
@@ -197,10 +225,41 @@ fn handle_llm_responses(
 ) {
     for response in llm_responses.read() {
         if let Some(_context) = thought_system.pending_requests.remove(&response.id) {
-            // Clean up the response text
-            let thought = response.result.trim().to_string();
+            thought_system.streaming_thoughts.remove(&response.id);
+            thought_system.suppressed_streams.remove(&response.id);
+
+            // Already cleaned up by the output filters and safety policy
+            let thought = response.result.clone();
+            let support_resource = response
+                .safety
+                .as_ref()
+                .and_then(|intervention| intervention.support_resource.clone());
+
+            update_thoughts.write(ThoughtGeneratedEvent {
+                text: thought,
+                support_resource,
+            });
+        }
+    }
+}
 
-            update_thoughts.write(ThoughtGeneratedEvent { text: thought });
+fn handle_llm_rejections(
+    mut llm_rejections: EventReader<AiGenerationRejected>,
+    mut thought_system: ResMut<ThoughtGenerationSystem>,
+    mut update_thoughts: EventWriter<ThoughtGeneratedEvent>,
+) {
+    for rejection in llm_rejections.read() {
+        if thought_system
+            .pending_requests
+            .remove(&rejection.id)
+            .is_some()
+        {
+            thought_system.streaming_thoughts.remove(&rejection.id);
+            thought_system.suppressed_streams.remove(&rejection.id);
+
+            warn!("Thought {} rejected: {}", rejection.id, rejection.reason);
+
+            update_thoughts.write(ThoughtGeneratedEvent::new(FALLBACK_THOUGHT));
         }
     }
 }
@@ -211,6 +270,14 @@ fn handle_async_llm_responses(
     mut update_thoughts: EventWriter<ThoughtGeneratedEvent>,
 ) {
     for response in async_responses.read() {
+        // The model-based safety check only runs on the final text,
+        // so nothing is streamed to the player while it is enabled
+        if thought_system.safety_policy.model_check
+            || thought_system.suppressed_streams.contains(&response.id)
+        {
+            continue;
+        }
+
         // Check if this is a request we're tracking
         if thought_system.pending_requests.contains_key(&response.id) {
             // Accumulate the new token
@@ -220,12 +287,30 @@ fn handle_async_llm_responses(
                 .or_default();
 
             accumulated.push_str(&response.result);
+            let accumulated = accumulated.clone();
+
+            // Stop streaming as soon as the partial thought is flagged,
+            // the final response carries the replacement text
+            if thought_system
+                .safety_policy
+                .classifier
+                .classify(&accumulated)
+                .is_flagged()
+            {
+                thought_system.suppressed_streams.insert(response.id);
+                update_thoughts.write(ThoughtGeneratedEvent::new(
+                    thought_system.safety_policy.safe_text.clone(),
+                ));
+                continue;
+            }
 
             // Send the current accumulated thought to UI
-            // This will update the display with each new token
-            update_thoughts.write(ThoughtGeneratedEvent {
-                text: accumulated.clone(),
-            });
+            // This will update the display with each new token.
+            // Partial text goes through the same filters as the final one,
+            // a refused partial is simply not shown.
+            if let Ok(thought) = thought_system.output_filters.apply(&accumulated) {
+                update_thoughts.write(ThoughtGeneratedEvent::new(thought));
+            }
 
             // Note: We don't remove from pending_requests here because
             // async responses are incremental. The final response will
@@ -303,6 +388,36 @@ When active symptoms are mentioned, incorporate them naturally into thoughts wit
 Respond with a realistic internal thought for the given situation."#.to_string()
 }
 
+// Post-processing applied to every generated thought before it reaches the UI
+fn get_thought_output_filters() -> OutputFilterPipeline {
+    OutputFilterPipeline::new()
+        .with(OutputFilter::StripChatMarkers)
+        .with(OutputFilter::NormalizeQuotes)
+        .with(OutputFilter::NormalizeWhitespace)
+        .with(OutputFilter::RejectPromptLeak {
+            reference: get_character_system_prompt(),
+            window: 6,
+        })
+        .with(OutputFilter::BanWords(vec![
+            "as an ai".to_string(),
+            "language model".to_string(),
+            "hikikomori character".to_string(),
+        ]))
+        .with(OutputFilter::MaxLength {
+            max_chars: MAX_THOUGHT_CHARS,
+        })
+}
+
+// Guardrail for self-harm content in generated thoughts
+fn get_thought_safety_policy() -> SafetyPolicy {
+    SafetyPolicy {
+        safe_text: "Everything feels heavy right now. I just need to get through this moment."
+            .to_string(),
+        support_resource: Some(SUPPORT_RESOURCE.to_string()),
+        ..default()
+    }
+}
+
 // Generate context-specific prompts with symptom awareness
 fn generate_thought_prompt(context: &ThoughtContext) -> String {
     let state = &context.current_state;
@@ -370,7 +485,7 @@ fn listen_for_mood_changes(
     mut mood_events: EventReader<MoodChangedEvent>,
     thought_events: EventWriter<GenerateThoughtEvent>,
     mut action_log: ResMut<ActionLog>,
-    game_state: Res<GameState>,
+    rules: Res<GameRules>,
 ) {
     for event in mood_events.read() {
         // Log the mood change
@@ -380,7 +495,7 @@ fn listen_for_mood_changes(
                 "Mood changed from {:?} to {:?}",
                 event.old_mood, event.new_mood
             ),
-            &game_state,
+            &rules.state,
         );
 
         // Generate thought about mood change
@@ -395,7 +510,7 @@ fn listen_for_resource_crises(
     mut resource_events: EventReader<ResourceChangedEvent>,
     thought_events: EventWriter<GenerateThoughtEvent>,
     mut action_log: ResMut<ActionLog>,
-    game_state: Res<GameState>,
+    rules: Res<GameRules>,
     mut last_crisis_states: Local<std::collections::HashMap<ResourceType, bool>>,
 ) {
     for event in resource_events.read() {
@@ -414,7 +529,7 @@ fn listen_for_resource_crises(
                     "{:?} reached crisis level: {:.1}",
                     event.resource_type, event.new_value
                 ),
-                &game_state,
+                &rules.state,
             );
 
             // Generate desperate thought
@@ -474,6 +589,7 @@ fn listen_for_phase_changes(
             GamePhase::TurnOver => {}
             GamePhase::AdversaryCardDraw => {}
             GamePhase::AdversaryCardSelection => {}
+            GamePhase::DeckBuilding => {}
         }
     }
 }
@@ -482,7 +598,7 @@ fn listen_for_card_selections(
     mut card_events: EventReader<CardSelectedEvent>,
     mut thought_events: EventWriter<GenerateThoughtEvent>,
     mut action_log: ResMut<ActionLog>,
-    game_state: Res<GameState>,
+    rules: Res<GameRules>,
 ) {
     for event in card_events.read() {
         let card = event.0.clone();
@@ -490,7 +606,7 @@ fn listen_for_card_selections(
         action_log.log_action(
             ActionType::CardPlayed(card.clone()),
             format!("Selected card: {}", card.name),
-            &game_state,
+            &rules.state,
         );
 
         // Generate thought about the card choice
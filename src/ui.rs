@@ -1,13 +1,21 @@
 use crate::{
     AppState,
-    cards::{CrisisLevel, Mood, ResourceType, StatusEffect},
+    cards::{
+        BlockedCard, CrisisLevel, LockKind, MULLIGAN_MENTAL_COST, Mood, MulliganSelection,
+        ResourceType, StatusEffectDefinition,
+    },
     logic::{
-        ActiveStatusEffect, CutsceneEndEvent, CutsceneStartEvent, GamePhase, GamePhaseState,
-        GameState, TurnOverEvent,
+        ActiveStatusEffect, CardSelectionError, CutsceneEndEvent, CutsceneStartEvent, Game,
+        GamePhase, GameRules, GameState, PassTurnEvent, TurnOverEvent,
     },
     thoughts::ThoughtGeneratedEvent,
 };
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_la_mesa::events::{CardHover, CardOut};
+
+/// How long a toast stays on screen
+const TOAST_SECONDS: f32 = 3.0;
 
 pub struct GameUIPlugin;
 
@@ -15,6 +23,7 @@ impl Plugin for GameUIPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<UpdateThoughtsEvent>()
             .add_event::<EndTurnEvent>()
+            .add_event::<ToggleMulliganEvent>()
             .add_systems(OnEnter(AppState::Game), setup_ui)
             .add_systems(
                 Update,
@@ -25,6 +34,11 @@ impl Plugin for GameUIPlugin {
                     handle_button_interactions,
                     update_end_turn_button_visibility,
                     handle_end_turn_button,
+                    update_mulligan_button,
+                    handle_mulligan_button_interactions,
+                    show_blocked_card_tooltip,
+                    show_card_rejection_toast,
+                    expire_toasts,
                 )
                     .run_if(in_state(AppState::Game)),
             );
@@ -45,6 +59,10 @@ impl UpdateThoughtsEvent {
 #[derive(Event)]
 pub struct EndTurnEvent;
 
+/// Start marking hand cards for a mulligan, or redraw the marked ones
+#[derive(Event)]
+pub struct ToggleMulliganEvent;
+
 // Consolidated UI colors
 pub struct UIColors;
 
@@ -58,6 +76,7 @@ impl UIColors {
     pub const BUTTON_HOVERED: Color = Color::srgb(0.5, 0.8, 1.0);
     pub const BUTTON_PRESSED: Color = Color::srgb(0.3, 0.6, 0.9);
     pub const BUTTON_DISABLED: Color = Color::srgb(0.3, 0.3, 0.3);
+    pub const LOCKED_TEXT: Color = Color::srgb(0.95, 0.75, 0.3);
 
     // Symptom colors for visual distinction
     pub const SYMPTOM_BACKGROUND: Color = Color::srgba(0.8, 0.3, 0.3, 0.2);
@@ -98,21 +117,6 @@ impl UIColors {
         Color::srgb(0.9, 0.2, 0.2), // Critical - Red
     ];
 
-    const STATUS_EFFECT_COLORS: [Color; 12] = [
-        Color::srgb(0.3, 0.4, 0.8), // Insomnia - Blue
-        Color::srgb(0.8, 0.3, 0.3), // Sick - Red
-        Color::srgb(0.4, 0.8, 0.4), // Motivated - Green
-        Color::srgb(0.8, 0.6, 0.2), // Overwhelmed - Orange
-        Color::srgb(0.8, 0.2, 0.8), // Addicted - Purple
-        Color::srgb(0.6, 0.6, 0.3), // Exhausted - Brown
-        Color::srgb(0.8, 0.8, 0.2), // Anxious - Yellow
-        Color::srgb(0.4, 0.4, 0.6), // Depressed - Dark Blue
-        Color::srgb(0.9, 0.3, 0.6), // Manic - Pink
-        Color::srgb(0.5, 0.7, 0.5), // Stable - Light Green
-        Color::srgb(0.3, 0.7, 0.9), // Focused - Cyan
-        Color::srgb(0.9, 0.5, 0.2), // Hungry - Orange-Red
-    ];
-
     pub fn mood_color(mood: &Mood) -> Color {
         Self::MOOD_COLORS[*mood as usize]
     }
@@ -125,39 +129,11 @@ impl UIColors {
         Self::CRISIS_COLORS[*crisis_level as usize]
     }
 
-    pub fn status_effect_color(effect: &StatusEffect) -> Color {
-        let index = match effect {
-            StatusEffect::Insomnia(_) => 0,
-            StatusEffect::Sick(_) => 1,
-            StatusEffect::Motivated(_) => 2,
-            StatusEffect::Overwhelmed(_) => 3,
-            StatusEffect::Addicted(_, _) => 4,
-            StatusEffect::Exhausted(_) => 5,
-            StatusEffect::Anxious(_) => 6,
-            StatusEffect::Depressed(_) => 7,
-            StatusEffect::Manic(_) => 8,
-            StatusEffect::Stable(_) => 9,
-            StatusEffect::Focused(_) => 10,
-            StatusEffect::Hungry(_) => 11,
-        };
-        Self::STATUS_EFFECT_COLORS[index]
-    }
-
-    pub fn status_effect_name(effect: &StatusEffect) -> String {
-        match effect {
-            StatusEffect::Insomnia(_) => "Insomnia".to_string(),
-            StatusEffect::Sick(_) => "Sick".to_string(),
-            StatusEffect::Motivated(_) => "Motivated".to_string(),
-            StatusEffect::Overwhelmed(_) => "Overwhelmed".to_string(),
-            StatusEffect::Addicted(substance, _) => format!("Addicted ({})", substance),
-            StatusEffect::Exhausted(_) => "Exhausted".to_string(),
-            StatusEffect::Anxious(_) => "Anxious".to_string(),
-            StatusEffect::Depressed(_) => "Depressed".to_string(),
-            StatusEffect::Manic(_) => "Manic".to_string(),
-            StatusEffect::Stable(_) => "Stable".to_string(),
-            StatusEffect::Focused(_) => "Focused".to_string(),
-            StatusEffect::Hungry(_) => "Hungry".to_string(),
-        }
+    /// Color of a status effect from its definition, hex like `#4C66CC`
+    pub fn status_effect_color(definition: Option<&StatusEffectDefinition>) -> Color {
+        definition
+            .and_then(|definition| Srgba::hex(&definition.color).ok())
+            .map_or(Self::TEXT_DIM, Color::Srgba)
     }
 
     pub fn resource_colors(resource_type: ResourceType) -> (Color, Color) {
@@ -217,6 +193,10 @@ pub struct CharacterThoughts {
     pub clear_timer: Option<Timer>,
 }
 
+/// Where to find help, shown under a thought the safety policy had to replace
+#[derive(Component)]
+pub struct SupportResourceText;
+
 #[derive(Component)]
 pub struct AvatarImage;
 
@@ -236,6 +216,19 @@ pub struct ResourceValueText {
 #[derive(Component)]
 pub struct EndTurnButton;
 
+#[derive(Component)]
+pub struct MulliganButton;
+
+#[derive(Component)]
+pub struct MulliganButtonText;
+
+#[derive(Component)]
+pub struct BlockedCardTooltip;
+
+/// Short message at the top of the screen, gone once the timer runs out
+#[derive(Component)]
+pub struct Toast(pub Timer);
+
 fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
     spawn_left_panel(&mut commands);
     spawn_thoughts_panel(&mut commands, &asset_server);
@@ -302,6 +295,36 @@ fn spawn_left_panel(commands: &mut Commands) {
                             ));
                         });
 
+                    // Mulligan Button - Only shown while a card can be picked
+                    panel
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Px(40.0),
+                                margin: UiRect::bottom(Val::Px(16.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(UIColors::BUTTON_NORMAL),
+                            BorderColor(UIColors::BUTTON_NORMAL),
+                            Visibility::Hidden,
+                            MulliganButton,
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::new("Mulligan"),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                                MulliganButtonText,
+                            ));
+                        });
+
                     spawn_text_section(
                         panel,
                         "Turn 1",
@@ -413,13 +436,32 @@ fn spawn_left_panel(commands: &mut Commands) {
         });
 }
 
+/// The turn can be ended once it is over, or right away when nothing in the hand can be played
+fn end_turn_available(rules: &Game) -> bool {
+    let phase_state = &rules.phase;
+    !phase_state.cutscene_active
+        && match phase_state.current_phase {
+            GamePhase::TurnOver => true,
+            GamePhase::CardSelection => !rules.has_playable_card(),
+            _ => false,
+        }
+}
+
 fn handle_end_turn_button(
     mut end_turn_events: EventReader<EndTurnEvent>,
     mut turn_over_events: EventWriter<TurnOverEvent>,
-    phase_state: Res<GamePhaseState>,
+    mut pass_turn_events: EventWriter<PassTurnEvent>,
+    rules: Res<GameRules>,
 ) {
     for _event in end_turn_events.read() {
-        if !phase_state.cutscene_active {
+        if !end_turn_available(&rules) {
+            continue;
+        }
+
+        if rules.phase.current_phase == GamePhase::CardSelection {
+            info!("End Turn button pressed with nothing playable - passing the turn");
+            pass_turn_events.write(PassTurnEvent);
+        } else {
             info!("End Turn button pressed - processing turn over");
             turn_over_events.write(TurnOverEvent);
         }
@@ -509,6 +551,22 @@ fn spawn_thoughts_panel(commands: &mut Commands, asset_server: &Res<AssetServer>
                                 CharacterThoughts::default(),
                             ));
                         });
+
+                    // Kept apart from the thought so it doesn't count against its length
+                    thoughts_section.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(UIColors::TEXT),
+                        Node {
+                            max_width: Val::Px(550.0),
+                            margin: UiRect::top(Val::Px(4.0)),
+                            ..default()
+                        },
+                        SupportResourceText,
+                    ));
                 });
         });
 }
@@ -604,13 +662,11 @@ fn spawn_resource_bar(parent: &mut ChildSpawnerCommands, label: &str, resource_t
 
 fn update_end_turn_button_visibility(
     mut button_query: Query<&mut Visibility, With<EndTurnButton>>,
-    phase_state: Res<GamePhaseState>,
+    rules: Res<GameRules>,
 ) {
-    if phase_state.is_changed() {
+    if rules.is_changed() {
         for mut visibility in button_query.iter_mut() {
-            *visibility = if matches!(phase_state.current_phase, GamePhase::TurnOver)
-                && !phase_state.cutscene_active
-            {
+            *visibility = if end_turn_available(&rules) {
                 Visibility::Inherited
             } else {
                 Visibility::Hidden
@@ -619,6 +675,202 @@ fn update_end_turn_button_visibility(
     }
 }
 
+/// The hand can be redrawn once per turn while a card is picked, if the mental cost is affordable
+fn update_mulligan_button(
+    mut button_query: Query<&mut Visibility, With<MulliganButton>>,
+    mut text_query: Query<&mut Text, With<MulliganButtonText>>,
+    rules: Res<GameRules>,
+    selection: Res<MulliganSelection>,
+) {
+    if !rules.is_changed() && !selection.is_changed() {
+        return;
+    }
+
+    let available = rules.can_mulligan();
+    for mut visibility in button_query.iter_mut() {
+        *visibility = if available {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    let label = match (selection.marking, selection.card_ids.len()) {
+        (false, _) => format!("Mulligan (-{MULLIGAN_MENTAL_COST:.0} Mental)"),
+        (true, 0) => "Pick cards to redraw, or cancel".to_string(),
+        (true, count) => format!("Redraw {count} card(s)"),
+    };
+    for mut text in text_query.iter_mut() {
+        text.0 = label.clone();
+    }
+}
+
+fn handle_mulligan_button_interactions(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<MulliganButton>),
+    >,
+    mut toggle_mulligan_events: EventWriter<ToggleMulliganEvent>,
+    rules: Res<GameRules>,
+) {
+    for (interaction, mut background_color, mut border_color) in &mut interaction_query {
+        if rules.phase.current_phase != GamePhase::CardSelection || rules.phase.cutscene_active {
+            continue;
+        }
+
+        match *interaction {
+            Interaction::Pressed => {
+                background_color.0 = UIColors::BUTTON_PRESSED;
+                border_color.0 = UIColors::BUTTON_PRESSED;
+                toggle_mulligan_events.write(ToggleMulliganEvent);
+            }
+            Interaction::Hovered => {
+                background_color.0 = UIColors::BUTTON_HOVERED;
+                border_color.0 = UIColors::BUTTON_HOVERED;
+            }
+            Interaction::None => {
+                background_color.0 = UIColors::BUTTON_NORMAL;
+                border_color.0 = UIColors::BUTTON_NORMAL;
+            }
+        }
+    }
+}
+
+/// Hovering a greyed out card lists what keeps it from being played
+fn show_blocked_card_tooltip(
+    mut commands: Commands,
+    mut card_hover_events: EventReader<CardHover>,
+    mut card_out_events: EventReader<CardOut>,
+    q_blocked_cards: Query<&BlockedCard>,
+    q_tooltips: Query<Entity, With<BlockedCardTooltip>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+) {
+    let hovered: Vec<Entity> = card_hover_events.read().map(|event| event.entity).collect();
+    if card_out_events.read().count() == 0 && hovered.is_empty() {
+        return;
+    }
+
+    for entity in q_tooltips.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let Some(blocked) = hovered
+        .last()
+        .and_then(|entity| q_blocked_cards.get(*entity).ok())
+    else {
+        return;
+    };
+    let cursor = q_window
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .unwrap_or_default();
+
+    commands
+        .spawn((
+            UIRoot,
+            BlockedCardTooltip,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(cursor.x + 16.0),
+                top: Val::Px((cursor.y - 120.0).max(0.0)),
+                max_width: Val::Px(320.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(5.0)),
+            BackgroundColor::from(UIColors::BACKGROUND),
+            BorderColor(UIColors::SYMPTOM_BORDER),
+            GlobalZIndex(10),
+            Name::new("Blocked Card Tooltip"),
+        ))
+        .with_children(|tooltip| {
+            tooltip.spawn((
+                Text::new("Can't play this now"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(UIColors::SYMPTOM_TEXT),
+            ));
+            for condition in &blocked.blocking_conditions {
+                tooltip.spawn((
+                    Text::new(format!("- {condition}")),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(UIColors::TEXT),
+                ));
+            }
+        });
+}
+
+/// Tell the player why the card they tried to play was turned down
+fn show_card_rejection_toast(
+    mut commands: Commands,
+    mut card_selection_errors: EventReader<CardSelectionError>,
+    q_toasts: Query<Entity, With<Toast>>,
+) {
+    for event in card_selection_errors.read() {
+        for entity in q_toasts.iter() {
+            commands.entity(entity).despawn();
+        }
+
+        let mut message = format!("Can't play {}", event.card.name);
+        if !event.blocking_conditions.is_empty() {
+            message = format!("{message}: {}", event.blocking_conditions.join(", "));
+        }
+
+        commands
+            .spawn((
+                UIRoot,
+                Toast(Timer::from_seconds(TOAST_SECONDS, TimerMode::Once)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    top: Val::Px(24.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                GlobalZIndex(10),
+                Name::new("Card Rejection Toast"),
+            ))
+            .with_children(|toast| {
+                toast.spawn((
+                    Text::new(message),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(UIColors::SYMPTOM_TEXT),
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderRadius::all(Val::Px(5.0)),
+                    BackgroundColor::from(UIColors::BACKGROUND),
+                    BorderColor(UIColors::SYMPTOM_BORDER),
+                ));
+            });
+    }
+}
+
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_toasts: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in q_toasts.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 // Consolidated update systems (removed button state logic since we're using visibility now)
 fn update_displays(
     mut commands: Commands,
@@ -629,7 +881,7 @@ fn update_displays(
         Query<&mut Text, (With<DayDisplay>, Without<TimeDisplay>)>,
         Query<(&mut Text, &mut TextColor), With<MoodDisplay>>,
         Query<(&mut Text, &mut TextColor), (With<CrisisDisplay>, Without<MoodDisplay>)>,
-        Query<(&mut Text, &ResourceValueText)>,
+        Query<(&mut Text, &mut TextColor, &ResourceValueText)>,
     )>,
     mut fill_query: Query<
         (&mut Node, &mut BackgroundColor),
@@ -641,13 +893,15 @@ fn update_displays(
     // NEW: Symptoms panel queries
     symptoms_panel_query: Query<(Entity, &Children), With<SymptomsPanel>>,
     symptom_items_query: Query<Entity, With<SymptomItem>>,
-    phase_state: Res<GamePhaseState>,
-    game_state: Res<GameState>,
+    rules: Res<GameRules>,
     asset_server: Res<AssetServer>,
     mut avatar_query: Query<&mut ImageNode, With<AvatarImage>>,
 ) {
+    let phase_state = &rules.phase;
+    let game_state = &rules.state;
+
     // Update phase display
-    if phase_state.is_changed() {
+    if rules.is_changed() {
         for (mut text, mut color) in text_queries.p0().iter_mut() {
             *text = Text::new(format!("Phase: {}", phase_state.get_phase_name()));
             *color = TextColor(UIColors::phase_color(&phase_state.current_phase));
@@ -659,7 +913,7 @@ fn update_displays(
     }
 
     // Update game state displays
-    if game_state.is_changed() {
+    if rules.is_changed() {
         // Update avatar based on mood
         for mut image in avatar_query.iter_mut() {
             let avatar_path = match game_state.current_mood {
@@ -709,9 +963,25 @@ fn update_displays(
         }
 
         // Resource value text
-        for (mut text, resource_value) in text_queries.p6().iter_mut() {
+        for (mut text, mut color, resource_value) in text_queries.p6().iter_mut() {
             let value = game_state.get_resource_value(resource_value.resource_type);
-            *text = Text::new(format!("{value:.0}/100"));
+            match game_state
+                .locked_resources
+                .get(&resource_value.resource_type)
+            {
+                Some(lock) => {
+                    let kind = match lock.kind {
+                        LockKind::NoGain => "no gain",
+                        LockKind::NoSpend => "no spend",
+                    };
+                    *text = Text::new(format!("{value:.0}/100 locked: {kind}, {}t", lock.turns));
+                    *color = TextColor(UIColors::LOCKED_TEXT);
+                }
+                None => {
+                    *text = Text::new(format!("{value:.0}/100"));
+                    *color = TextColor(UIColors::TEXT);
+                }
+            }
         }
 
         // Resource bars
@@ -732,17 +1002,18 @@ fn update_displays(
             &mut commands,
             &symptoms_panel_query,
             &symptom_items_query,
-            &game_state,
+            game_state,
         );
     }
 
     // Update status effects display
-    if game_state.is_changed() {
+    if rules.is_changed() {
         update_status_effects_display(
             &mut commands,
             &status_effects_panel_query,
             &status_effect_items_query,
-            &game_state,
+            game_state,
+            &asset_server,
         );
     }
 }
@@ -753,14 +1024,11 @@ fn handle_button_interactions(
         (Changed<Interaction>, With<EndTurnButton>),
     >,
     mut end_turn_events: EventWriter<EndTurnEvent>,
-    phase_state: Res<GamePhaseState>,
+    rules: Res<GameRules>,
 ) {
     for (interaction, mut background_color, mut border_color) in &mut interaction_query {
-        // Only handle interactions if button is visible (TurnOver phase and not in cutscene)
-        let button_active = matches!(phase_state.current_phase, GamePhase::TurnOver)
-            && !phase_state.cutscene_active;
-
-        if !button_active {
+        // Only handle interactions if button is visible
+        if !end_turn_available(&rules) {
             continue;
         }
 
@@ -868,6 +1136,7 @@ fn update_status_effects_display(
     status_effects_panel_query: &Query<(Entity, &Children), With<StatusEffectsPanel>>,
     status_effect_items_query: &Query<Entity, With<StatusEffectItem>>,
     game_state: &GameState,
+    asset_server: &AssetServer,
 ) {
     // Clear existing status effect items
     for entity in status_effect_items_query.iter() {
@@ -894,14 +1163,28 @@ fn update_status_effects_display(
                 ));
             } else {
                 for status_effect in &game_state.status_effects {
-                    spawn_status_effect_item(panel, status_effect);
+                    let definition = game_state
+                        .status_effect_definitions
+                        .get(status_effect.effect);
+                    spawn_status_effect_item(panel, status_effect, definition, asset_server);
                 }
             }
         });
     }
 }
 
-fn spawn_status_effect_item(parent: &mut ChildSpawnerCommands, status_effect: &ActiveStatusEffect) {
+fn spawn_status_effect_item(
+    parent: &mut ChildSpawnerCommands,
+    status_effect: &ActiveStatusEffect,
+    definition: Option<&StatusEffectDefinition>,
+    asset_server: &AssetServer,
+) {
+    let color = UIColors::status_effect_color(definition);
+    let name = definition.map_or_else(
+        || format!("{:?}", status_effect.effect),
+        |definition| definition.name.clone(),
+    );
+
     parent
         .spawn((
             Node {
@@ -913,23 +1196,38 @@ fn spawn_status_effect_item(parent: &mut ChildSpawnerCommands, status_effect: &A
             },
             BorderRadius::new(Val::Px(3.), Val::Px(3.), Val::Px(3.), Val::Px(3.)),
             BackgroundColor(UIColors::BACKGROUND.with_alpha(0.3)),
-            BorderColor(UIColors::status_effect_color(&status_effect.effect).with_alpha(0.5)),
+            BorderColor(color.with_alpha(0.5)),
             StatusEffectItem,
         ))
         .with_children(|item| {
-            // Effect name
-            item.spawn((
-                Text::new(UIColors::status_effect_name(&status_effect.effect)),
-                TextFont {
-                    font_size: 12.0,
-                    ..default()
-                },
-                TextColor(UIColors::status_effect_color(&status_effect.effect)),
-                Node {
-                    margin: UiRect::bottom(Val::Px(2.0)),
-                    ..default()
-                },
-            ));
+            // Effect icon and name
+            item.spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                margin: UiRect::bottom(Val::Px(2.0)),
+                ..default()
+            })
+            .with_children(|row| {
+                if let Some(icon) = definition.and_then(|definition| definition.icon.as_ref()) {
+                    row.spawn((
+                        ImageNode::new(asset_server.load(icon)),
+                        Node {
+                            width: Val::Px(12.0),
+                            height: Val::Px(12.0),
+                            margin: UiRect::right(Val::Px(4.0)),
+                            ..default()
+                        },
+                    ));
+                }
+                row.spawn((
+                    Text::new(name),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+            });
 
             // Duration and intensity info
             let info_text = if status_effect.intensity != 1.0 {
@@ -994,10 +1292,14 @@ fn handle_events(
     mut er_cutscene_end: EventReader<CutsceneEndEvent>,
     mut ew_update_thoughts: EventWriter<UpdateThoughtsEvent>,
     q_ui_roots: Query<(Entity, &UIRoot)>,
+    mut q_support_resource: Query<&mut Text, With<SupportResourceText>>,
 ) {
     // Handle thought events
     for event in er_thought.read() {
         ew_update_thoughts.write(UpdateThoughtsEvent::new(event.text.clone()));
+        for mut text in &mut q_support_resource {
+            *text = Text::new(event.support_resource.clone().unwrap_or_default());
+        }
     }
 
     // Handle cutscene events